@@ -14,4 +14,22 @@ pub enum FactoryError {
     IdenticalTokens = 8,
     UpgradeTimelockNotExpired = 9,
     Unauthorized = 10,
+    InvalidProtocolFeeShare = 11,
+    InvalidFeeParams = 12,
+    InvalidThreshold = 13,
+    DuplicateSigner = 14,
+    ProposalNotFound = 15,
+    ProposalAlreadyExecuted = 16,
+    AlreadyApproved = 17,
+    NoPendingUpgrade = 18,
+    InvalidUpgradeDelay = 19,
+    FeeTierNotEnabled = 20,
+    TooManyFeeTiers = 21,
+    NoPendingTransfer = 22,
+    TokenDenied = 23,
+    CreatorNotAllowed = 24,
+    TokenNotAllowed = 25,
+    InvalidAmount = 26,
+    ProposalExpired = 27,
+    ProposalCancelled = 28,
 }
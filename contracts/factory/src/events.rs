@@ -1,4 +1,5 @@
-use soroban_sdk::{Address, Env};
+use crate::storage::{CreationPolicy, FeeParams, Role, UpgradeTarget};
+use soroban_sdk::{Address, Env, Vec};
 
 #[allow(dead_code)]
 pub struct FactoryEvents;
@@ -9,10 +10,12 @@ impl FactoryEvents {
         env: &Env,
         token_a: &Address,
         token_b: &Address,
+        fee_tier: u32,
         pair: &Address,
         pair_index: u32,
     ) {
-        let topics = (soroban_sdk::symbol_short!("created"), token_a.clone(), token_b.clone());
+        let topics =
+            (soroban_sdk::symbol_short!("created"), token_a.clone(), token_b.clone(), fee_tier);
         env.events().publish(topics, (pair.clone(), pair_index));
     }
 
@@ -35,11 +38,93 @@ impl FactoryEvents {
         env.events().publish((soroban_sdk::symbol_short!("upgraded"),), new_version);
     }
 
+    pub fn pair_migrated(env: &Env, pair: &Address, code_version: u32) {
+        env.events().publish((soroban_sdk::symbol_short!("pair_mig"), pair.clone()), code_version);
+    }
+
+    pub fn upgrade_cancelled(env: &Env, target: UpgradeTarget) {
+        env.events().publish((soroban_sdk::symbol_short!("upg_cncl"),), target);
+    }
+
+    pub fn upgrade_delay_set(env: &Env, delay_seconds: u64) {
+        env.events().publish((soroban_sdk::symbol_short!("upg_delay"),), delay_seconds);
+    }
+
     pub fn fee_to_set(env: &Env, new_fee_to: &Option<Address>) {
         env.events().publish((soroban_sdk::symbol_short!("fee_to"),), new_fee_to.clone());
     }
 
+    pub fn fee_to_setter_proposed(env: &Env, new_setter: &Address) {
+        env.events().publish((soroban_sdk::symbol_short!("setter_pr"),), new_setter.clone());
+    }
+
+    pub fn role_proposed(env: &Env, role: Role, account: &Address) {
+        env.events().publish((soroban_sdk::symbol_short!("role_prop"), role), account.clone());
+    }
+
+    pub fn role_accepted(env: &Env, role: Role, account: &Address) {
+        env.events().publish((soroban_sdk::symbol_short!("role_acc"), role), account.clone());
+    }
+
+    pub fn role_revoked(env: &Env, role: Role) {
+        env.events().publish((soroban_sdk::symbol_short!("role_rvk"), role), ());
+    }
+
+    pub fn creation_policy_set(env: &Env, policy: CreationPolicy) {
+        env.events().publish((soroban_sdk::symbol_short!("policy"),), policy);
+    }
+
+    pub fn creator_allowed_set(env: &Env, creator: &Address, allowed: bool) {
+        env.events().publish((soroban_sdk::symbol_short!("creator"), creator.clone()), allowed);
+    }
+
+    pub fn token_allowed_set(env: &Env, token: &Address, allowed: bool) {
+        env.events().publish((soroban_sdk::symbol_short!("tok_allow"), token.clone()), allowed);
+    }
+
+    pub fn token_denied_set(env: &Env, token: &Address, denied: bool) {
+        env.events().publish((soroban_sdk::symbol_short!("tok_deny"), token.clone()), denied);
+    }
+
     pub fn fee_to_setter_set(env: &Env, new_setter: &Address) {
         env.events().publish((soroban_sdk::symbol_short!("setter"),), new_setter.clone());
     }
+
+    pub fn protocol_fee_share_set(env: &Env, share_bps: u32) {
+        env.events().publish((soroban_sdk::symbol_short!("fee_shr"),), share_bps);
+    }
+
+    pub fn fee_tier_set(env: &Env, fee_tier: u32, params: &FeeParams) {
+        env.events().publish((soroban_sdk::symbol_short!("fee_tier"), fee_tier), params.clone());
+    }
+
+    pub fn pair_fee_params_set(env: &Env, pair: &Address, params: &FeeParams) {
+        env.events().publish((soroban_sdk::symbol_short!("fee_prm"), pair.clone()), params.clone());
+    }
+
+    pub fn proposal_created(env: &Env, proposal_id: u32, proposer: &Address) {
+        env.events()
+            .publish((soroban_sdk::symbol_short!("proposed"), proposal_id), proposer.clone());
+    }
+
+    pub fn proposal_approved(env: &Env, proposal_id: u32, signer: &Address) {
+        env.events().publish((soroban_sdk::symbol_short!("approved"), proposal_id), signer.clone());
+    }
+
+    pub fn proposal_executed(env: &Env, proposal_id: u32) {
+        env.events().publish((soroban_sdk::symbol_short!("executed"), proposal_id), ());
+    }
+
+    pub fn proposal_cancelled(env: &Env, proposal_id: u32) {
+        env.events().publish((soroban_sdk::symbol_short!("cancelled"), proposal_id), ());
+    }
+
+    pub fn signers_set(env: &Env, signers: &Vec<Address>, threshold: u32) {
+        env.events()
+            .publish((soroban_sdk::symbol_short!("signers"),), (signers.clone(), threshold));
+    }
+
+    pub fn pair_paused_set(env: &Env, pair: &Address, paused: bool) {
+        env.events().publish((soroban_sdk::symbol_short!("pr_paused"), pair.clone()), paused);
+    }
 }
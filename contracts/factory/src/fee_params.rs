@@ -0,0 +1,99 @@
+use crate::{errors::FactoryError, storage::FeeParams};
+
+/// Fixed-point scale used by the pair's EMA alpha (1e14 = 100%).
+const SCALE: i128 = 100_000_000_000_000;
+/// Hard ceiling on any pair fee (10%).
+pub const MAX_FEE_BPS: u32 = 1_000;
+/// Upper bound on how aggressively volatility ramps the fee.
+pub const MAX_RAMP_UP_MULTIPLIER: u32 = 100;
+
+/// Fee tiers enabled at initialization, in bps: stable, standard and exotic.
+pub const STABLE_FEE_TIER: u32 = 1;
+pub const STANDARD_FEE_TIER: u32 = 30;
+pub const EXOTIC_FEE_TIER: u32 = 100;
+
+/// The fee tiers enabled at initialization, with their presets.
+pub fn default_fee_tiers() -> [(u32, FeeParams); 3] {
+    [
+        (STABLE_FEE_TIER, stable_fee_params()),
+        (STANDARD_FEE_TIER, default_fee_params()),
+        (EXOTIC_FEE_TIER, exotic_fee_params()),
+    ]
+}
+
+/// Preset for correlated assets: a 1 bps baseline inside a 1–10 bps band,
+/// ramping gently since volatility between stable assets is usually noise.
+pub fn stable_fee_params() -> FeeParams {
+    FeeParams {
+        ema_alpha: SCALE / 20,
+        baseline_fee_bps: STABLE_FEE_TIER,
+        min_fee_bps: 1,
+        max_fee_bps: 10,
+        ramp_up_multiplier: 1,
+        cooldown_divisor: 2,
+        decay_threshold_blocks: 100,
+    }
+}
+
+/// Preset for the standard tier.
+///
+/// 5% EMA alpha, a 30 bps baseline inside a 5–100 bps band, and the
+/// accumulator halving every 100 idle ledgers.
+pub fn default_fee_params() -> FeeParams {
+    FeeParams {
+        ema_alpha: SCALE / 20,
+        baseline_fee_bps: 30,
+        min_fee_bps: 5,
+        max_fee_bps: 100,
+        ramp_up_multiplier: 2,
+        cooldown_divisor: 2,
+        decay_threshold_blocks: 100,
+    }
+}
+
+/// Preset for long-tail assets: a 100 bps baseline inside a 50–300 bps band,
+/// ramping faster to compensate LPs for volatile markets.
+pub fn exotic_fee_params() -> FeeParams {
+    FeeParams {
+        ema_alpha: SCALE / 20,
+        baseline_fee_bps: EXOTIC_FEE_TIER,
+        min_fee_bps: 50,
+        max_fee_bps: 300,
+        ramp_up_multiplier: 3,
+        cooldown_divisor: 2,
+        decay_threshold_blocks: 100,
+    }
+}
+
+/// Validates a fee tier preset: the parameters must pass [`validate`] and
+/// their baseline must be the tier's nominal fee.
+pub fn validate_tier(fee_tier: u32, params: &FeeParams) -> Result<(), FactoryError> {
+    validate(params)?;
+    if params.baseline_fee_bps != fee_tier {
+        return Err(FactoryError::InvalidFeeParams);
+    }
+    Ok(())
+}
+
+/// Validates a dynamic fee configuration before it reaches any pair.
+///
+/// Rejects parameters that would make the fee engine misbehave: an alpha
+/// outside `(0, SCALE]`, a baseline outside `[min, max]`, a ceiling above
+/// [`MAX_FEE_BPS`], a ramp multiplier outside `[1, MAX_RAMP_UP_MULTIPLIER]`,
+/// a cooldown divisor that would not decay (`< 2`), or a zero decay period.
+pub fn validate(params: &FeeParams) -> Result<(), FactoryError> {
+    let valid = params.ema_alpha > 0
+        && params.ema_alpha <= SCALE
+        && params.min_fee_bps <= params.baseline_fee_bps
+        && params.baseline_fee_bps <= params.max_fee_bps
+        && params.max_fee_bps <= MAX_FEE_BPS
+        && (1..=MAX_RAMP_UP_MULTIPLIER).contains(&params.ramp_up_multiplier)
+        && params.cooldown_divisor >= 2
+        && params.decay_threshold_blocks > 0;
+
+    if valid {
+        Ok(())
+    } else {
+        Err(FactoryError::InvalidFeeParams)
+    }
+}
@@ -1,12 +1,85 @@
 use crate::errors::FactoryError;
+use crate::storage::{FactoryStorage, Proposal, MAX_PROPOSAL_AGE, MAX_SIGNERS};
 use soroban_sdk::{Address, Env, Vec};
 
-/// 2-of-3 multi-sig verification.
-#[allow(dead_code)]
+/// Default approval threshold for `signer_count` signers: a simple majority,
+/// so three signers need two approvals.
+pub fn default_threshold(signer_count: u32) -> u32 {
+    signer_count / 2 + 1
+}
+
+/// Validates a signer set and threshold: 1–10 distinct signers and
+/// `1 <= threshold <= signers.len()`.
+pub fn validate_signers(
+    env: &Env,
+    signers: &Vec<Address>,
+    threshold: u32,
+) -> Result<(), FactoryError> {
+    if signers.is_empty() || signers.len() > MAX_SIGNERS {
+        return Err(FactoryError::InvalidSignerCount);
+    }
+    let mut seen = Vec::new(env);
+    for signer in signers.iter() {
+        if seen.contains(&signer) {
+            return Err(FactoryError::DuplicateSigner);
+        }
+        seen.push_back(signer);
+    }
+    if threshold == 0 || threshold > signers.len() {
+        return Err(FactoryError::InvalidThreshold);
+    }
+    Ok(())
+}
+
+/// Requires `signer` to authorize the call and to be a registered signer.
+pub fn require_signer(storage: &FactoryStorage, signer: &Address) -> Result<(), FactoryError> {
+    signer.require_auth();
+    if !storage.signers.contains(signer) {
+        return Err(FactoryError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// M-of-N multi-sig verification for approvers co-signing one transaction.
+///
+/// Every approver must be a distinct registered signer and authorize the
+/// call; at least `storage.threshold` of them are required.
 pub fn verify_multisig(
-    _env: &Env,
-    _signers: &Vec<Address>,
-    _required: u32,
+    env: &Env,
+    storage: &FactoryStorage,
+    approvers: &Vec<Address>,
 ) -> Result<(), FactoryError> {
-    todo!()
+    let mut seen = Vec::new(env);
+    for approver in approvers.iter() {
+        if seen.contains(&approver) {
+            return Err(FactoryError::DuplicateSigner);
+        }
+        require_signer(storage, &approver)?;
+        seen.push_back(approver);
+    }
+    if seen.len() < storage.threshold {
+        return Err(FactoryError::InsufficientSignatures);
+    }
+    Ok(())
+}
+
+/// Counts the approvals that still belong to current signers, so approvals
+/// from signers removed after they approved no longer count.
+pub fn count_approvals(storage: &FactoryStorage, approvals: &Vec<Address>) -> u32 {
+    approvals.iter().filter(|approver| storage.signers.contains(approver)).count() as u32
+}
+
+/// Fails unless `proposal` can still be approved or executed: it must not be
+/// executed, cancelled, or older than `MAX_PROPOSAL_AGE`.
+pub fn require_open(env: &Env, proposal: &Proposal) -> Result<(), FactoryError> {
+    if proposal.executed {
+        return Err(FactoryError::ProposalAlreadyExecuted);
+    }
+    if proposal.cancelled {
+        return Err(FactoryError::ProposalCancelled);
+    }
+    if env.ledger().timestamp() > proposal.created_at.saturating_add(MAX_PROPOSAL_AGE) {
+        return Err(FactoryError::ProposalExpired);
+    }
+    Ok(())
 }
@@ -5,17 +5,29 @@ extern crate std;
 
 mod errors;
 mod events;
+mod fee_params;
 mod governance;
+mod lp_metadata;
+mod policy;
+mod roles;
 mod storage;
 mod upgrade;
 
 #[cfg(test)]
 mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 
 use errors::FactoryError;
+use soroban_sdk::token::TokenClient;
 use soroban_sdk::xdr::ToXdr;
-use soroban_sdk::{contract, contractclient, contractimpl, Address, Bytes, BytesN, Env, Vec};
-use storage::FactoryStorage;
+use soroban_sdk::{
+    contract, contractclient, contractimpl, Address, Bytes, BytesN, Env, String, Vec,
+};
+use storage::{
+    CreationPolicy, FactoryStorage, FeeParams, GovernanceAction, Proposal, Role, TimelockedAction,
+    UpgradeTarget,
+};
 
 #[contractclient(name = "PairClient")]
 pub trait PairInterface {
@@ -25,7 +37,30 @@ pub trait PairInterface {
         token_a: Address,
         token_b: Address,
         lp_token: Address,
+        fee_params: FeeParams,
     ) -> Result<(), FactoryError>;
+    fn set_fee_params(env: Env, fee_params: FeeParams) -> Result<(), FactoryError>;
+    fn set_paused(env: Env, paused: bool) -> Result<(), FactoryError>;
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), FactoryError>;
+    fn migrate(env: Env) -> Result<u32, FactoryError>;
+    fn mint(env: Env, to: Address) -> Result<i128, FactoryError>;
+}
+
+#[contractclient(name = "LpTokenClient")]
+pub trait LpTokenInterface {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        decimals: u32,
+        name: String,
+        symbol: String,
+    ) -> Result<(), FactoryError>;
+}
+
+/// SEP-41 metadata read from the underlying tokens to name LP tokens.
+#[contractclient(name = "TokenMetadataClient")]
+pub trait TokenMetadataInterface {
+    fn symbol(env: Env) -> String;
 }
 
 #[contract]
@@ -45,23 +80,35 @@ impl Factory {
             return Err(FactoryError::AlreadyInitialized);
         }
 
-        // Validate signers: must have between 1 and 10 (inclusive)
-        let signer_count = signers.len();
-        if !(1..=10).contains(&signer_count) {
-            return Err(FactoryError::InvalidSignerCount);
-        }
+        // Validate signers: 1 to 10 distinct addresses, majority threshold
+        let threshold = governance::default_threshold(signers.len());
+        governance::validate_signers(&env, &signers, threshold)?;
 
-        let factory_storage = FactoryStorage {
+        let mut factory_storage = FactoryStorage {
             signers,
+            threshold,
+            proposal_count: 0,
             pair_wasm_hash,
             lp_token_wasm_hash,
             pair_count: 0,
             protocol_version: 1,
+            pair_code_version: 1,
+            upgrade_delay: storage::DEFAULT_UPGRADE_DELAY,
             paused: false,
             fee_to: None,
             fee_to_setter,
+            pending_fee_to_setter: None,
+            protocol_fee_share_bps: storage::DEFAULT_PROTOCOL_FEE_SHARE_BPS,
+            fee_tiers: Vec::new(&env),
+            creation_policy: CreationPolicy::Open,
         };
 
+        // Enable the stable, standard and exotic fee tiers
+        for (fee_tier, params) in fee_params::default_fee_tiers() {
+            factory_storage.fee_tiers.push_back(fee_tier);
+            storage::set_fee_tier(&env, fee_tier, &params);
+        }
+
         storage::set_factory_storage(&env, &factory_storage);
 
         // Extend instance TTL to keep contract alive
@@ -70,10 +117,64 @@ impl Factory {
         Ok(())
     }
 
+    /// Deploys the pair for `(token_a, token_b)` in `fee_tier`, seeded with
+    /// that tier's fee preset. Each token pair may have one pair per tier.
+    ///
+    /// `creator` must satisfy the factory's creation policy, and neither
+    /// token may be denylisted. Its auth is only required under the creator
+    /// allowlist.
     pub fn create_pair(
         env: Env,
+        creator: Address,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+    ) -> Result<Address, FactoryError> {
+        if Self::creation_policy(env.clone()) == CreationPolicy::CreatorAllowlist {
+            creator.require_auth();
+        }
+        Self::deploy_pair(&env, &creator, token_a, token_b, fee_tier)
+    }
+
+    /// Creates the pair for `(token_a, token_b)` in `fee_tier` and seeds it
+    /// with `amount_a` and `amount_b` from `creator`, in one call.
+    ///
+    /// Creating and seeding atomically means nobody can slip in between and
+    /// set the initial price. The first LP position is minted to `creator`.
+    ///
+    /// # Returns
+    /// `(pair, liquidity)`: the new pair's address and the LP tokens minted.
+    pub fn create_pair_and_seed(
+        env: Env,
+        creator: Address,
         token_a: Address,
         token_b: Address,
+        fee_tier: u32,
+        amount_a: i128,
+        amount_b: i128,
+    ) -> Result<(Address, i128), FactoryError> {
+        if amount_a <= 0 || amount_b <= 0 {
+            return Err(FactoryError::InvalidAmount);
+        }
+        creator.require_auth();
+
+        let pair = Self::deploy_pair(&env, &creator, token_a.clone(), token_b.clone(), fee_tier)?;
+
+        TokenClient::new(&env, &token_a).transfer(&creator, &pair, &amount_a);
+        TokenClient::new(&env, &token_b).transfer(&creator, &pair, &amount_b);
+        let liquidity = PairClient::new(&env, &pair).mint(&creator);
+
+        Ok((pair, liquidity))
+    }
+
+    /// Deploys, initializes and registers a new pair once `creator` has
+    /// passed whatever auth the creation policy needs.
+    fn deploy_pair(
+        env: &Env,
+        creator: &Address,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
     ) -> Result<Address, FactoryError> {
         if token_a == token_b {
             return Err(FactoryError::IdenticalTokens);
@@ -82,21 +183,28 @@ impl Factory {
         let (token_0, token_1) =
             if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
 
-        if storage::get_pair(&env, token_0.clone(), token_1.clone()).is_some() {
+        if storage::get_pair(env, token_0.clone(), token_1.clone(), fee_tier).is_some() {
             return Err(FactoryError::PairExists);
         }
 
-        let mut factory_storage =
-            storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+        let factory_storage =
+            storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
 
         if factory_storage.paused {
             return Err(FactoryError::ProtocolPaused);
         }
 
+        policy::check_create(env, factory_storage.creation_policy, creator, &token_0, &token_1)?;
+
+        if storage::get_fee_tier(env, fee_tier).is_none() {
+            return Err(FactoryError::FeeTierNotEnabled);
+        }
+
         // 1. Deploy Pair
-        let mut salt_data = Bytes::new(&env);
-        salt_data.append(&token_0.clone().to_xdr(&env));
-        salt_data.append(&token_1.clone().to_xdr(&env));
+        let mut salt_data = Bytes::new(env);
+        salt_data.append(&token_0.clone().to_xdr(env));
+        salt_data.append(&token_1.clone().to_xdr(env));
+        salt_data.append(&fee_tier.to_xdr(env));
         let salt = env.crypto().sha256(&salt_data);
 
         let pair_address = env
@@ -105,8 +213,8 @@ impl Factory {
             .deploy(factory_storage.pair_wasm_hash.clone());
 
         // 2. Deploy LP Token
-        let mut lp_salt_data = Bytes::new(&env);
-        lp_salt_data.append(&pair_address.clone().to_xdr(&env));
+        let mut lp_salt_data = Bytes::new(env);
+        lp_salt_data.append(&pair_address.clone().to_xdr(env));
         let lp_salt = env.crypto().sha256(&lp_salt_data);
 
         let lp_token_address = env
@@ -114,49 +222,138 @@ impl Factory {
             .with_current_contract(lp_salt)
             .deploy(factory_storage.lp_token_wasm_hash.clone());
 
-        // 3. Initialize Pair
-        let pair_client = PairClient::new(&env, &pair_address);
-        pair_client.initialize(
+        // 3. Initialize and register both
+        Self::install_pair(env, &token_0, &token_1, fee_tier, &pair_address, &lp_token_address)?;
+
+        Ok(pair_address)
+    }
+
+    /// Initializes a freshly deployed pair and its LP token, then registers
+    /// the pair under `(token_0, token_1, fee_tier)`. Tokens must be sorted.
+    pub(crate) fn install_pair(
+        env: &Env,
+        token_0: &Address,
+        token_1: &Address,
+        fee_tier: u32,
+        pair_address: &Address,
+        lp_token_address: &Address,
+    ) -> Result<(), FactoryError> {
+        let mut factory_storage =
+            storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+        let fee_params =
+            storage::get_fee_tier(env, fee_tier).ok_or(FactoryError::FeeTierNotEnabled)?;
+
+        // 1. Initialize Pair
+        PairClient::new(env, pair_address).initialize(
             &env.current_contract_address(),
-            &token_0,
-            &token_1,
-            &lp_token_address,
+            token_0,
+            token_1,
+            lp_token_address,
+            &fee_params,
         );
 
-        // 4. Store pair
-        storage::set_pair(&env, token_0.clone(), token_1.clone(), pair_address.clone());
-        storage::set_pair(&env, token_1.clone(), token_0.clone(), pair_address.clone());
+        // 2. Initialize LP Token, with the pair as its sole minter
+        let (lp_name, lp_symbol) = lp_metadata::lp_metadata(env, token_0, token_1);
+        LpTokenClient::new(env, lp_token_address).initialize(
+            pair_address,
+            &lp_metadata::LP_TOKEN_DECIMALS,
+            &lp_name,
+            &lp_symbol,
+        );
 
+        // 3. Store pair
         let pair_index = factory_storage.pair_count;
+        storage::register_pair(env, pair_index, token_0, token_1, fee_tier, pair_address);
+        storage::set_pair_code_version(env, pair_address, factory_storage.pair_code_version);
         factory_storage.pair_count += 1;
-        storage::set_factory_storage(&env, &factory_storage);
+        storage::set_factory_storage(env, &factory_storage);
 
-        // 5. Emit event
-        events::FactoryEvents::pair_created(&env, &token_0, &token_1, &pair_address, pair_index);
+        // 4. Emit event
+        events::FactoryEvents::pair_created(
+            env,
+            token_0,
+            token_1,
+            fee_tier,
+            pair_address,
+            pair_index,
+        );
 
-        Ok(pair_address)
+        Ok(())
     }
 
-    pub fn get_pair(env: Env, token_a: Address, token_b: Address) -> Option<Address> {
-        storage::get_pair(&env, token_a, token_b)
+    pub fn get_pair(
+        env: Env,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+    ) -> Option<Address> {
+        storage::get_pair(&env, token_a, token_b, fee_tier)
     }
 
-    pub fn pause(env: Env, _signers: Vec<Address>) -> Result<(), FactoryError> {
-        let mut storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
-        // TODO: Auth check for signers
-        storage.paused = true;
-        storage::set_factory_storage(&env, &storage);
-        events::FactoryEvents::paused(&env);
-        Ok(())
+    /// Returns the pair created at `index`, matching the index emitted in the
+    /// `pair_created` event, or `None` past the end.
+    pub fn all_pairs(env: Env, index: u32) -> Option<Address> {
+        storage::get_pair_at(&env, index)
     }
 
-    pub fn unpause(env: Env, _signers: Vec<Address>) -> Result<(), FactoryError> {
-        let mut storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
-        // TODO: Auth check for signers
-        storage.paused = false;
-        storage::set_factory_storage(&env, &storage);
-        events::FactoryEvents::unpaused(&env);
-        Ok(())
+    /// Returns the number of pairs created so far.
+    pub fn all_pairs_length(env: Env) -> u32 {
+        storage::get_factory_storage(&env).map(|s| s.pair_count).unwrap_or(0)
+    }
+
+    /// Returns up to `limit` pairs in creation order, starting at `start`.
+    ///
+    /// `limit` is capped at `MAX_PAGE_SIZE`; a short or empty page means the
+    /// end of the registry was reached.
+    pub fn pairs_paged(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let end = start
+            .saturating_add(limit.min(storage::MAX_PAGE_SIZE))
+            .min(Self::all_pairs_length(env.clone()));
+
+        let mut pairs = Vec::new(&env);
+        for index in start..end {
+            if let Some(pair) = storage::get_pair_at(&env, index) {
+                pairs.push_back(pair);
+            }
+        }
+        pairs
+    }
+
+    /// Returns the number of pairs containing `token`.
+    pub fn pairs_for_token_length(env: Env, token: Address) -> u32 {
+        storage::get_token_pair_count(&env, &token)
+    }
+
+    /// Returns up to `limit` pairs containing `token` in creation order,
+    /// starting at the token's `start`-th pair.
+    ///
+    /// `limit` is capped at `MAX_PAGE_SIZE`; a short or empty page means the
+    /// end of the token's pairs was reached.
+    pub fn pairs_for_token(env: Env, token: Address, start: u32, limit: u32) -> Vec<Address> {
+        let end = start
+            .saturating_add(limit.min(storage::MAX_PAGE_SIZE))
+            .min(storage::get_token_pair_count(&env, &token));
+
+        let mut pairs = Vec::new(&env);
+        for index in start..end {
+            if let Some(pair) = storage::get_token_pair_at(&env, &token, index) {
+                pairs.push_back(pair);
+            }
+        }
+        pairs
+    }
+
+    /// Pauses the protocol. Requires `threshold` co-signing governance signers.
+    ///
+    /// While paused, `create_pair` reverts and pairs reject `mint`, `swap` and
+    /// `flash_loan`.
+    pub fn pause(env: Env, signers: Vec<Address>) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::Pause)
+    }
+
+    /// Unpauses the protocol. Requires `threshold` co-signing governance signers.
+    pub fn unpause(env: Env, signers: Vec<Address>) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::Unpause)
     }
 
     pub fn set_fee_to(
@@ -180,6 +377,10 @@ impl Factory {
         Ok(())
     }
 
+    /// Proposes `new_setter` as the next `fee_to_setter`.
+    ///
+    /// The current setter stays in charge until `new_setter` calls
+    /// `accept_fee_to_setter`. A later proposal replaces a pending one.
     pub fn set_fee_to_setter(
         env: Env,
         setter: Address,
@@ -193,7 +394,29 @@ impl Factory {
             return Err(FactoryError::Unauthorized);
         }
 
+        storage.pending_fee_to_setter = Some(new_setter.clone());
+        storage::set_factory_storage(&env, &storage);
+
+        events::FactoryEvents::fee_to_setter_proposed(&env, &new_setter);
+
+        Ok(())
+    }
+
+    /// Completes a `set_fee_to_setter` transfer. Must be called by the
+    /// proposed setter.
+    pub fn accept_fee_to_setter(env: Env, new_setter: Address) -> Result<(), FactoryError> {
+        let mut storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+
+        new_setter.require_auth();
+
+        match &storage.pending_fee_to_setter {
+            None => return Err(FactoryError::NoPendingTransfer),
+            Some(pending) if *pending != new_setter => return Err(FactoryError::Unauthorized),
+            Some(_) => {}
+        }
+
         storage.fee_to_setter = new_setter.clone();
+        storage.pending_fee_to_setter = None;
         storage::set_factory_storage(&env, &storage);
 
         events::FactoryEvents::fee_to_setter_set(&env, &new_setter);
@@ -201,6 +424,468 @@ impl Factory {
         Ok(())
     }
 
+    /// Sets the share of LP fee growth, in basis points, minted to `fee_to`.
+    ///
+    /// Pairs read this on every `mint` and `burn` while `fee_to` is set. Only
+    /// `fee_to_setter` may change it, and it is capped at
+    /// `MAX_PROTOCOL_FEE_SHARE_BPS` so LPs always keep the majority of fees.
+    pub fn set_protocol_fee_share(
+        env: Env,
+        setter: Address,
+        share_bps: u32,
+    ) -> Result<(), FactoryError> {
+        let mut storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+
+        setter.require_auth();
+
+        if setter != storage.fee_to_setter {
+            return Err(FactoryError::Unauthorized);
+        }
+
+        if share_bps == 0 || share_bps > storage::MAX_PROTOCOL_FEE_SHARE_BPS {
+            return Err(FactoryError::InvalidProtocolFeeShare);
+        }
+
+        storage.protocol_fee_share_bps = share_bps;
+        storage::set_factory_storage(&env, &storage);
+
+        events::FactoryEvents::protocol_fee_share_set(&env, share_bps);
+
+        Ok(())
+    }
+
+    /// Enables `fee_tier`, or replaces the preset `create_pair` applies to its
+    /// new pairs.
+    ///
+    /// Requires `threshold` co-signing governance signers. Parameters are
+    /// bounds-checked by `fee_params::validate_tier`; existing pairs are
+    /// unaffected.
+    pub fn set_fee_tier(
+        env: Env,
+        signers: Vec<Address>,
+        fee_tier: u32,
+        params: FeeParams,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetFeeTier(fee_tier, params))
+    }
+
+    /// Updates the dynamic fee parameters of an already deployed pair.
+    ///
+    /// Requires `threshold` co-signing governance signers. The pair only
+    /// accepts the update from its own factory, so foreign pairs reject it.
+    pub fn set_pair_fee_params(
+        env: Env,
+        signers: Vec<Address>,
+        pair: Address,
+        params: FeeParams,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetPairFeeParams(pair, params))
+    }
+
+    /// Pauses or unpauses a single pair, independently of the global pause.
+    ///
+    /// Requires `threshold` co-signing governance signers. While paused, the
+    /// pair rejects `mint`, `swap` and `flash_loan` but still allows `burn`.
+    pub fn set_pair_paused(
+        env: Env,
+        signers: Vec<Address>,
+        pair: Address,
+        paused: bool,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetPairPaused(pair, paused))
+    }
+
+    /// Replaces the governance signer set and approval threshold.
+    ///
+    /// Requires `threshold` co-signing signers from the *current* set. The new
+    /// set must hold 1–10 distinct signers and `1 <= threshold <= len`.
+    pub fn set_signers(
+        env: Env,
+        signers: Vec<Address>,
+        new_signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(
+            &env,
+            &signers,
+            GovernanceAction::SetSigners(new_signers, threshold),
+        )
+    }
+
+    // ── Creation policy ───────────────────────────────────────────────────────
+
+    /// Sets who may create pairs. Requires `threshold` co-signing governance
+    /// signers.
+    pub fn set_creation_policy(
+        env: Env,
+        signers: Vec<Address>,
+        policy: CreationPolicy,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetCreationPolicy(policy))
+    }
+
+    /// Adds `creator` to or removes it from the creator allowlist. Requires
+    /// `threshold` co-signing governance signers.
+    pub fn set_creator_allowed(
+        env: Env,
+        signers: Vec<Address>,
+        creator: Address,
+        allowed: bool,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(
+            &env,
+            &signers,
+            GovernanceAction::SetCreatorAllowed(creator, allowed),
+        )
+    }
+
+    /// Adds `token` to or removes it from the token allowlist. Requires
+    /// `threshold` co-signing governance signers.
+    pub fn set_token_allowed(
+        env: Env,
+        signers: Vec<Address>,
+        token: Address,
+        allowed: bool,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(
+            &env,
+            &signers,
+            GovernanceAction::SetTokenAllowed(token, allowed),
+        )
+    }
+
+    /// Adds `token` to or removes it from the denylist. Requires `threshold`
+    /// co-signing governance signers.
+    ///
+    /// Denylisting only blocks new pairs; existing pairs keep trading.
+    pub fn set_token_denied(
+        env: Env,
+        signers: Vec<Address>,
+        token: Address,
+        denied: bool,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetTokenDenied(token, denied))
+    }
+
+    pub fn creation_policy(env: Env) -> CreationPolicy {
+        storage::get_factory_storage(&env)
+            .map(|s| s.creation_policy)
+            .unwrap_or(CreationPolicy::Open)
+    }
+
+    pub fn is_creator_allowed(env: Env, creator: Address) -> bool {
+        policy::is_creator_allowed(&env, &creator)
+    }
+
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        policy::is_token_allowed(&env, &token)
+    }
+
+    pub fn is_token_denied(env: Env, token: Address) -> bool {
+        policy::is_token_denied(&env, &token)
+    }
+
+    // ── Roles ─────────────────────────────────────────────────────────────────
+
+    /// Proposes `account` as the holder of `role`. Requires the admin role;
+    /// the signers can do the same through `GovernanceAction::ProposeRole`.
+    ///
+    /// The role changes hands once `account` calls `accept_role`.
+    pub fn propose_role(
+        env: Env,
+        admin: Address,
+        role: Role,
+        account: Address,
+    ) -> Result<(), FactoryError> {
+        roles::require_role(&env, Role::Admin, &admin)?;
+        roles::propose(&env, role, &account);
+        Ok(())
+    }
+
+    /// Accepts a pending `propose_role` for `account`.
+    pub fn accept_role(env: Env, account: Address, role: Role) -> Result<(), FactoryError> {
+        roles::accept(&env, role, &account)
+    }
+
+    /// Removes `role`'s holder and any pending proposal. Requires the admin
+    /// role; the signers can do the same through `GovernanceAction::RevokeRole`.
+    pub fn revoke_role(env: Env, admin: Address, role: Role) -> Result<(), FactoryError> {
+        roles::require_role(&env, Role::Admin, &admin)?;
+        roles::revoke(&env, role);
+        Ok(())
+    }
+
+    /// Pauses the protocol immediately. Requires the guardian role; only the
+    /// signers can unpause.
+    pub fn guardian_pause(env: Env, guardian: Address) -> Result<(), FactoryError> {
+        Self::execute_as_role(&env, Role::Guardian, &guardian, GovernanceAction::Pause)
+    }
+
+    /// Updates the dynamic fee parameters of a deployed pair. Requires the
+    /// fee manager role.
+    pub fn fee_manager_set_pair_fee_params(
+        env: Env,
+        fee_manager: Address,
+        pair: Address,
+        params: FeeParams,
+    ) -> Result<(), FactoryError> {
+        Self::execute_as_role(
+            &env,
+            Role::FeeManager,
+            &fee_manager,
+            GovernanceAction::SetPairFeeParams(pair, params),
+        )
+    }
+
+    /// Proposes a timelocked upgrade of `target`, exactly as the signers'
+    /// `propose_upgrade` does. Requires the upgrader role.
+    pub fn upgrader_propose_upgrade(
+        env: Env,
+        upgrader: Address,
+        target: UpgradeTarget,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        Self::execute_as_role(
+            &env,
+            Role::Upgrader,
+            &upgrader,
+            GovernanceAction::ProposeUpgrade(target, new_wasm_hash),
+        )
+    }
+
+    /// Cancels the pending upgrade of `target`. Requires the upgrader role.
+    pub fn upgrader_cancel_upgrade(
+        env: Env,
+        upgrader: Address,
+        target: UpgradeTarget,
+    ) -> Result<(), FactoryError> {
+        Self::execute_as_role(
+            &env,
+            Role::Upgrader,
+            &upgrader,
+            GovernanceAction::CancelUpgrade(target),
+        )
+    }
+
+    pub fn role(env: Env, role: Role) -> Option<Address> {
+        storage::get_role_holder(&env, role)
+    }
+
+    pub fn pending_role(env: Env, role: Role) -> Option<Address> {
+        storage::get_pending_role(&env, role)
+    }
+
+    // ── Upgrades ──────────────────────────────────────────────────────────────
+
+    /// Proposes upgrading `target` to `new_wasm_hash` once `upgrade_delay`
+    /// has elapsed. Requires `threshold` co-signing governance signers.
+    pub fn propose_upgrade(
+        env: Env,
+        signers: Vec<Address>,
+        target: UpgradeTarget,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(
+            &env,
+            &signers,
+            GovernanceAction::ProposeUpgrade(target, new_wasm_hash),
+        )
+    }
+
+    /// Cancels the pending upgrade of `target`. Requires `threshold`
+    /// co-signing governance signers.
+    pub fn cancel_upgrade(
+        env: Env,
+        signers: Vec<Address>,
+        target: UpgradeTarget,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::CancelUpgrade(target))
+    }
+
+    /// Sets the timelock applied to future upgrade proposals, between one and
+    /// thirty days. Requires `threshold` co-signing governance signers.
+    pub fn set_upgrade_delay(
+        env: Env,
+        signers: Vec<Address>,
+        delay_seconds: u64,
+    ) -> Result<(), FactoryError> {
+        Self::execute_with_signers(&env, &signers, GovernanceAction::SetUpgradeDelay(delay_seconds))
+    }
+
+    /// Executes the pending upgrade of `target` after its timelock expires.
+    ///
+    /// Permissionless: governance approval happened at proposal time, and the
+    /// delay gives users a window to exit before new code takes effect.
+    ///
+    /// # Returns
+    /// The new `protocol_version`.
+    pub fn execute_upgrade(env: Env, target: UpgradeTarget) -> Result<u32, FactoryError> {
+        upgrade::execute_upgrade(&env, target)
+    }
+
+    /// Upgrades pairs `start..start + limit` (by creation order) that still
+    /// run older pair code to the current `pair_wasm_hash`, then migrates
+    /// their stored data.
+    ///
+    /// Permissionless: the target code already passed governance and the
+    /// upgrade timelock. Pairs already on the current version are skipped,
+    /// so repeating a range is harmless. At most `MAX_MIGRATION_BATCH` pairs
+    /// are visited per call.
+    ///
+    /// # Returns
+    /// The number of pairs upgraded.
+    pub fn migrate_pairs(env: Env, start: u32, limit: u32) -> Result<u32, FactoryError> {
+        let factory = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+        let end =
+            start.saturating_add(limit.min(storage::MAX_MIGRATION_BATCH)).min(factory.pair_count);
+
+        let mut migrated = 0;
+        for index in start..end {
+            let pair = storage::get_pair_at(&env, index).ok_or(FactoryError::NotInitialized)?;
+            let version = storage::get_pair_code_version(&env, &pair).unwrap_or(1);
+            if version >= factory.pair_code_version {
+                continue;
+            }
+
+            let client = PairClient::new(&env, &pair);
+            client.upgrade(&factory.pair_wasm_hash);
+            client.migrate();
+            storage::set_pair_code_version(&env, &pair, factory.pair_code_version);
+            events::FactoryEvents::pair_migrated(&env, &pair, factory.pair_code_version);
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    /// Returns the pair code version `pair` runs, or `None` if the factory
+    /// did not create it.
+    pub fn pair_code_version(env: Env, pair: Address) -> Option<u32> {
+        storage::get_pair_code_version(&env, &pair)
+    }
+
+    /// Returns the version of the pair code new pairs are deployed with.
+    pub fn current_pair_code_version(env: Env) -> u32 {
+        storage::get_factory_storage(&env).map(|s| s.pair_code_version).unwrap_or(1)
+    }
+
+    pub fn pending_upgrade(env: Env, target: UpgradeTarget) -> Option<TimelockedAction> {
+        storage::get_pending_upgrade(&env, target)
+    }
+
+    pub fn upgrade_delay(env: Env) -> u64 {
+        storage::get_factory_storage(&env)
+            .map(|s| s.upgrade_delay)
+            .unwrap_or(storage::DEFAULT_UPGRADE_DELAY)
+    }
+
+    pub fn protocol_version(env: Env) -> u32 {
+        storage::get_factory_storage(&env).map(|s| s.protocol_version).unwrap_or(0)
+    }
+
+    // ── Proposals ─────────────────────────────────────────────────────────────
+
+    /// Records a governance proposal for signers to approve over time.
+    ///
+    /// Use this instead of the co-signed entrypoints when signers cannot sign
+    /// the same transaction. The proposer's approval is counted immediately,
+    /// and the action executes as soon as `threshold` approvals are reached.
+    /// Proposals that don't reach the threshold within `MAX_PROPOSAL_AGE`
+    /// (7 days) expire, so a stale action can't be revived by a late approval.
+    ///
+    /// # Returns
+    /// The new proposal's id.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        action: GovernanceAction,
+    ) -> Result<u32, FactoryError> {
+        let mut storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+        governance::require_signer(&storage, &proposer)?;
+        Self::validate_action(&env, &action)?;
+
+        let id = storage.proposal_count;
+        storage.proposal_count += 1;
+        storage::set_factory_storage(&env, &storage);
+
+        let proposal = Proposal {
+            action,
+            proposer: proposer.clone(),
+            approvals: Vec::from_array(&env, [proposer.clone()]),
+            created_at: env.ledger().timestamp(),
+            executed: false,
+            cancelled: false,
+        };
+        storage::set_proposal(&env, id, &proposal);
+        events::FactoryEvents::proposal_created(&env, id, &proposer);
+
+        Self::execute_if_approved(&env, id, proposal)?;
+        Ok(id)
+    }
+
+    /// Approves a pending proposal, executing it once `threshold` current
+    /// signers have approved.
+    ///
+    /// # Returns
+    /// `true` if this approval caused the proposal to execute.
+    pub fn approve(env: Env, signer: Address, proposal_id: u32) -> Result<bool, FactoryError> {
+        let storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+        governance::require_signer(&storage, &signer)?;
+
+        let mut proposal =
+            storage::get_proposal(&env, proposal_id).ok_or(FactoryError::ProposalNotFound)?;
+        governance::require_open(&env, &proposal)?;
+        if proposal.approvals.contains(&signer) {
+            return Err(FactoryError::AlreadyApproved);
+        }
+
+        proposal.approvals.push_back(signer.clone());
+        storage::set_proposal(&env, proposal_id, &proposal);
+        events::FactoryEvents::proposal_approved(&env, proposal_id, &signer);
+
+        Self::execute_if_approved(&env, proposal_id, proposal)
+    }
+
+    /// Withdraws a pending proposal so it can never execute. Only its
+    /// proposer can cancel it, and only while they are still a signer.
+    pub fn cancel(env: Env, proposer: Address, proposal_id: u32) -> Result<(), FactoryError> {
+        let storage = storage::get_factory_storage(&env).ok_or(FactoryError::NotInitialized)?;
+        governance::require_signer(&storage, &proposer)?;
+
+        let mut proposal =
+            storage::get_proposal(&env, proposal_id).ok_or(FactoryError::ProposalNotFound)?;
+        if proposal.proposer != proposer {
+            return Err(FactoryError::Unauthorized);
+        }
+        governance::require_open(&env, &proposal)?;
+
+        proposal.cancelled = true;
+        storage::set_proposal(&env, proposal_id, &proposal);
+        events::FactoryEvents::proposal_cancelled(&env, proposal_id);
+        Ok(())
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        storage::get_proposal(&env, proposal_id)
+    }
+
+    pub fn signers(env: Env) -> Vec<Address> {
+        storage::get_factory_storage(&env).map(|s| s.signers).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn threshold(env: Env) -> u32 {
+        storage::get_factory_storage(&env).map(|s| s.threshold).unwrap_or(0)
+    }
+
+    /// Returns the enabled fee tiers, in bps.
+    pub fn fee_tiers(env: Env) -> Vec<u32> {
+        storage::get_factory_storage(&env).map(|s| s.fee_tiers).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the preset applied to new pairs of `fee_tier`, if enabled.
+    pub fn fee_tier_params(env: Env, fee_tier: u32) -> Option<FeeParams> {
+        storage::get_fee_tier(&env, fee_tier)
+    }
+
     pub fn fee_to(env: Env) -> Option<Address> {
         storage::get_factory_storage(&env).map(|s| s.fee_to).unwrap_or(None)
     }
@@ -209,7 +894,191 @@ impl Factory {
         storage::get_factory_storage(&env).map(|s| s.fee_to_setter)
     }
 
+    pub fn pending_fee_to_setter(env: Env) -> Option<Address> {
+        storage::get_factory_storage(&env).and_then(|s| s.pending_fee_to_setter)
+    }
+
+    pub fn protocol_fee_share_bps(env: Env) -> u32 {
+        storage::get_factory_storage(&env)
+            .map(|s| s.protocol_fee_share_bps)
+            .unwrap_or(storage::DEFAULT_PROTOCOL_FEE_SHARE_BPS)
+    }
+
     pub fn is_paused(env: Env) -> bool {
         storage::get_factory_storage(&env).map(|s| s.paused).unwrap_or(false)
     }
+
+    /// Extends the TTL of the factory's instance storage. Permissionless, so
+    /// keepers can keep the factory alive through quiet periods.
+    pub fn bump(env: Env) -> Result<(), FactoryError> {
+        if !storage::has_factory_storage(&env) {
+            return Err(FactoryError::NotInitialized);
+        }
+        storage::extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    // ── Governance internals ──────────────────────────────────────────────────
+
+    /// Executes `action` on behalf of `caller`, who must hold `role`.
+    fn execute_as_role(
+        env: &Env,
+        role: Role,
+        caller: &Address,
+        action: GovernanceAction,
+    ) -> Result<(), FactoryError> {
+        storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+        roles::require_role(env, role, caller)?;
+        Self::validate_action(env, &action)?;
+        Self::execute_action(env, action)
+    }
+
+    fn execute_with_signers(
+        env: &Env,
+        signers: &Vec<Address>,
+        action: GovernanceAction,
+    ) -> Result<(), FactoryError> {
+        let storage = storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+        governance::verify_multisig(env, &storage, signers)?;
+        Self::validate_action(env, &action)?;
+        Self::execute_action(env, action)
+    }
+
+    fn execute_if_approved(
+        env: &Env,
+        proposal_id: u32,
+        mut proposal: Proposal,
+    ) -> Result<bool, FactoryError> {
+        let storage = storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+        governance::require_open(env, &proposal)?;
+        if governance::count_approvals(&storage, &proposal.approvals) < storage.threshold {
+            return Ok(false);
+        }
+
+        proposal.executed = true;
+        storage::set_proposal(env, proposal_id, &proposal);
+        Self::execute_action(env, proposal.action)?;
+        events::FactoryEvents::proposal_executed(env, proposal_id);
+        Ok(true)
+    }
+
+    fn validate_action(env: &Env, action: &GovernanceAction) -> Result<(), FactoryError> {
+        match action {
+            GovernanceAction::SetFeeTier(fee_tier, params) => {
+                fee_params::validate_tier(*fee_tier, params)?;
+                let storage =
+                    storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+                if !storage.fee_tiers.contains(fee_tier)
+                    && storage.fee_tiers.len() >= storage::MAX_FEE_TIERS
+                {
+                    return Err(FactoryError::TooManyFeeTiers);
+                }
+                Ok(())
+            }
+            GovernanceAction::SetPairFeeParams(_, params) => fee_params::validate(params),
+            GovernanceAction::SetSigners(signers, threshold) => {
+                governance::validate_signers(env, signers, *threshold)
+            }
+            GovernanceAction::SetUpgradeDelay(delay_seconds) => {
+                upgrade::validate_delay(*delay_seconds)
+            }
+            GovernanceAction::CancelUpgrade(target) => {
+                match storage::get_pending_upgrade(env, *target) {
+                    Some(_) => Ok(()),
+                    None => Err(FactoryError::NoPendingUpgrade),
+                }
+            }
+            GovernanceAction::Pause
+            | GovernanceAction::Unpause
+            | GovernanceAction::SetPairPaused(_, _)
+            | GovernanceAction::ProposeUpgrade(_, _)
+            | GovernanceAction::ProposeRole(_, _)
+            | GovernanceAction::RevokeRole(_)
+            | GovernanceAction::SetCreationPolicy(_)
+            | GovernanceAction::SetCreatorAllowed(_, _)
+            | GovernanceAction::SetTokenAllowed(_, _)
+            | GovernanceAction::SetTokenDenied(_, _) => Ok(()),
+        }
+    }
+
+    fn execute_action(env: &Env, action: GovernanceAction) -> Result<(), FactoryError> {
+        let mut storage = storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+        match action {
+            GovernanceAction::Pause => {
+                storage.paused = true;
+                storage::set_factory_storage(env, &storage);
+                events::FactoryEvents::paused(env);
+            }
+            GovernanceAction::Unpause => {
+                storage.paused = false;
+                storage::set_factory_storage(env, &storage);
+                events::FactoryEvents::unpaused(env);
+            }
+            GovernanceAction::SetFeeTier(fee_tier, params) => {
+                if !storage.fee_tiers.contains(fee_tier) {
+                    storage.fee_tiers.push_back(fee_tier);
+                    storage::set_factory_storage(env, &storage);
+                }
+                storage::set_fee_tier(env, fee_tier, &params);
+                events::FactoryEvents::fee_tier_set(env, fee_tier, &params);
+            }
+            GovernanceAction::SetPairFeeParams(pair, params) => {
+                PairClient::new(env, &pair).set_fee_params(&params);
+                events::FactoryEvents::pair_fee_params_set(env, &pair, &params);
+            }
+            GovernanceAction::SetPairPaused(pair, paused) => {
+                PairClient::new(env, &pair).set_paused(&paused);
+                events::FactoryEvents::pair_paused_set(env, &pair, paused);
+            }
+            GovernanceAction::SetSigners(signers, threshold) => {
+                storage.signers = signers.clone();
+                storage.threshold = threshold;
+                storage::set_factory_storage(env, &storage);
+                events::FactoryEvents::signers_set(env, &signers, threshold);
+            }
+            GovernanceAction::ProposeUpgrade(target, new_wasm_hash) => {
+                upgrade::propose_upgrade(env, target, new_wasm_hash)?;
+            }
+            GovernanceAction::CancelUpgrade(target) => {
+                upgrade::cancel_upgrade(env, target)?;
+            }
+            GovernanceAction::SetUpgradeDelay(delay_seconds) => {
+                storage.upgrade_delay = delay_seconds;
+                storage::set_factory_storage(env, &storage);
+                events::FactoryEvents::upgrade_delay_set(env, delay_seconds);
+            }
+            GovernanceAction::ProposeRole(role, account) => {
+                roles::propose(env, role, &account);
+            }
+            GovernanceAction::RevokeRole(role) => {
+                roles::revoke(env, role);
+            }
+            GovernanceAction::SetCreationPolicy(policy) => {
+                storage.creation_policy = policy;
+                storage::set_factory_storage(env, &storage);
+                events::FactoryEvents::creation_policy_set(env, policy);
+            }
+            GovernanceAction::SetCreatorAllowed(creator, allowed) => {
+                storage::set_list_entry(
+                    env,
+                    &storage::DataKey::AllowedCreator(creator.clone()),
+                    allowed,
+                );
+                events::FactoryEvents::creator_allowed_set(env, &creator, allowed);
+            }
+            GovernanceAction::SetTokenAllowed(token, allowed) => {
+                storage::set_list_entry(
+                    env,
+                    &storage::DataKey::AllowedToken(token.clone()),
+                    allowed,
+                );
+                events::FactoryEvents::token_allowed_set(env, &token, allowed);
+            }
+            GovernanceAction::SetTokenDenied(token, denied) => {
+                storage::set_list_entry(env, &storage::DataKey::DeniedToken(token.clone()), denied);
+                events::FactoryEvents::token_denied_set(env, &token, denied);
+            }
+        }
+        Ok(())
+    }
 }
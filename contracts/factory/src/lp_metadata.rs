@@ -0,0 +1,62 @@
+use crate::TokenMetadataClient;
+use soroban_sdk::{Address, Env, String};
+
+/// Decimals of every LP token, matching Stellar asset contracts.
+pub const LP_TOKEN_DECIMALS: u32 = 7;
+/// Longest underlying symbol embedded in LP metadata; longer or non
+/// alphanumeric symbols are replaced by the address-based fallback.
+pub const MAX_TOKEN_SYMBOL_LEN: u32 = 12;
+/// Number of trailing strkey characters used when a token has no usable symbol.
+const FALLBACK_SYMBOL_LEN: usize = 5;
+/// Length of a strkey-encoded address.
+const STRKEY_LEN: usize = 56;
+
+const NAME_PREFIX: &[u8] = b"CORAL-LP ";
+const SYMBOL_PREFIX: &[u8] = b"CLP-";
+
+/// Longest name or symbol `lp_metadata` can produce.
+const MAX_METADATA_LEN: usize = NAME_PREFIX.len() + 2 * MAX_TOKEN_SYMBOL_LEN as usize + 1;
+
+/// Derives the LP token `(name, symbol)` for a pair, e.g.
+/// `("CORAL-LP USDC/XLM", "CLP-USDC-XLM")`.
+pub fn lp_metadata(env: &Env, token_0: &Address, token_1: &Address) -> (String, String) {
+    let mut buf_0 = [0u8; MAX_TOKEN_SYMBOL_LEN as usize];
+    let mut buf_1 = [0u8; MAX_TOKEN_SYMBOL_LEN as usize];
+    let symbol_0 = token_symbol(env, token_0, &mut buf_0);
+    let symbol_1 = token_symbol(env, token_1, &mut buf_1);
+
+    (
+        concat(env, NAME_PREFIX, symbol_0, b'/', symbol_1),
+        concat(env, SYMBOL_PREFIX, symbol_0, b'-', symbol_1),
+    )
+}
+
+/// Writes `token`'s SEP-41 symbol into `buf`, or the tail of its strkey if the
+/// symbol can't be read or is unsuitable, and returns the written slice.
+fn token_symbol<'a>(env: &Env, token: &Address, buf: &'a mut [u8]) -> &'a [u8] {
+    if let Ok(Ok(symbol)) = TokenMetadataClient::new(env, token).try_symbol() {
+        let len = symbol.len();
+        if len > 0 && len <= MAX_TOKEN_SYMBOL_LEN {
+            let symbol_buf = &mut buf[..len as usize];
+            symbol.copy_into_slice(symbol_buf);
+            if symbol_buf.iter().all(u8::is_ascii_alphanumeric) {
+                return &buf[..len as usize];
+            }
+        }
+    }
+
+    let mut strkey = [0u8; STRKEY_LEN];
+    token.to_string().copy_into_slice(&mut strkey);
+    buf[..FALLBACK_SYMBOL_LEN].copy_from_slice(&strkey[STRKEY_LEN - FALLBACK_SYMBOL_LEN..]);
+    &buf[..FALLBACK_SYMBOL_LEN]
+}
+
+fn concat(env: &Env, prefix: &[u8], first: &[u8], separator: u8, second: &[u8]) -> String {
+    let mut out = [0u8; MAX_METADATA_LEN];
+    let mut len = 0;
+    for part in [prefix, first, &[separator], second] {
+        out[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    }
+    String::from_bytes(env, &out[..len])
+}
@@ -0,0 +1,48 @@
+use crate::errors::FactoryError;
+use crate::storage::{self, CreationPolicy, DataKey};
+use soroban_sdk::{Address, Env};
+
+/// Checks that `creator` may create a pair of `token_0` and `token_1` under
+/// `policy`.
+///
+/// Denylisted tokens are rejected under every policy. Callers must have
+/// required `creator`'s auth when the policy is `CreatorAllowlist`.
+pub fn check_create(
+    env: &Env,
+    policy: CreationPolicy,
+    creator: &Address,
+    token_0: &Address,
+    token_1: &Address,
+) -> Result<(), FactoryError> {
+    if is_token_denied(env, token_0) || is_token_denied(env, token_1) {
+        return Err(FactoryError::TokenDenied);
+    }
+
+    match policy {
+        CreationPolicy::Open => Ok(()),
+        CreationPolicy::CreatorAllowlist => {
+            if !is_creator_allowed(env, creator) {
+                return Err(FactoryError::CreatorNotAllowed);
+            }
+            Ok(())
+        }
+        CreationPolicy::TokenAllowlist => {
+            if !is_token_allowed(env, token_0) || !is_token_allowed(env, token_1) {
+                return Err(FactoryError::TokenNotAllowed);
+            }
+            Ok(())
+        }
+    }
+}
+
+pub fn is_creator_allowed(env: &Env, creator: &Address) -> bool {
+    storage::has_list_entry(env, &DataKey::AllowedCreator(creator.clone()))
+}
+
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    storage::has_list_entry(env, &DataKey::AllowedToken(token.clone()))
+}
+
+pub fn is_token_denied(env: &Env, token: &Address) -> bool {
+    storage::has_list_entry(env, &DataKey::DeniedToken(token.clone()))
+}
@@ -0,0 +1,46 @@
+use crate::errors::FactoryError;
+use crate::events::FactoryEvents;
+use crate::storage::{self, Role};
+use soroban_sdk::{Address, Env};
+
+// Role-based access control.
+//
+// Each role has at most one holder. Roles change hands in two steps: the admin
+// (or the signer set) proposes an account, which then accepts with its own
+// auth, so a typo can never hand a role to an address nobody controls.
+
+/// Requires `caller` to authorize the call and to hold `role`.
+pub fn require_role(env: &Env, role: Role, caller: &Address) -> Result<(), FactoryError> {
+    caller.require_auth();
+    if storage::get_role_holder(env, role).as_ref() != Some(caller) {
+        return Err(FactoryError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Records `account` as the pending holder of `role`, replacing any earlier
+/// proposal.
+pub fn propose(env: &Env, role: Role, account: &Address) {
+    storage::set_pending_role(env, role, account);
+    FactoryEvents::role_proposed(env, role, account);
+}
+
+/// Makes `account` the holder of `role`, if it is the pending holder.
+pub fn accept(env: &Env, role: Role, account: &Address) -> Result<(), FactoryError> {
+    account.require_auth();
+    let pending = storage::get_pending_role(env, role).ok_or(FactoryError::NoPendingTransfer)?;
+    if pending != *account {
+        return Err(FactoryError::Unauthorized);
+    }
+    storage::remove_pending_role(env, role);
+    storage::set_role_holder(env, role, account);
+    FactoryEvents::role_accepted(env, role, account);
+    Ok(())
+}
+
+/// Clears `role`'s holder and any pending proposal for it.
+pub fn revoke(env: &Env, role: Role) {
+    storage::remove_role_holder(env, role);
+    storage::remove_pending_role(env, role);
+    FactoryEvents::role_revoked(env, role);
+}
@@ -1,26 +1,177 @@
-use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
 
-const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
-const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+
+/// Default share of LP fee growth minted to `fee_to` (~1/6, as in Uniswap V2).
+pub const DEFAULT_PROTOCOL_FEE_SHARE_BPS: u32 = 1_667;
+/// Upper bound on the protocol share so LPs always keep most of the fees.
+pub const MAX_PROTOCOL_FEE_SHARE_BPS: u32 = 5_000;
+/// Maximum number of governance signers.
+pub const MAX_SIGNERS: u32 = 10;
+/// Default delay between proposing and executing an upgrade (72h).
+pub const DEFAULT_UPGRADE_DELAY: u64 = 259_200;
+/// Bounds on the configurable upgrade delay: 1 day to 30 days.
+pub const MIN_UPGRADE_DELAY: u64 = 86_400;
+pub const MAX_UPGRADE_DELAY: u64 = 2_592_000;
+/// How long a proposal stays open for approvals (7 days). Older proposals
+/// can no longer be approved or executed.
+pub const MAX_PROPOSAL_AGE: u64 = 604_800;
+/// Maximum number of pairs `migrate_pairs` touches in one call.
+pub const MAX_MIGRATION_BATCH: u32 = 25;
+/// Maximum number of pairs `pairs_paged` and `pairs_for_token` return in one
+/// call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+/// Maximum number of enabled fee tiers.
+pub const MAX_FEE_TIERS: u32 = 10;
 
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct FactoryStorage {
     pub signers: Vec<Address>,
+    /// Number of distinct signer approvals required for privileged actions.
+    pub threshold: u32,
+    pub proposal_count: u32,
     pub pair_wasm_hash: BytesN<32>,
     pub lp_token_wasm_hash: BytesN<32>,
     pub pair_count: u32,
     pub protocol_version: u32,
+    /// Version of the pair code at `pair_wasm_hash`, bumped on each pair upgrade.
+    pub pair_code_version: u32,
+    /// Seconds a proposed upgrade must wait before it can be executed.
+    pub upgrade_delay: u64,
     pub paused: bool,
     pub fee_to: Option<Address>,
     pub fee_to_setter: Address,
+    /// Proposed `fee_to_setter`, pending its acceptance.
+    pub pending_fee_to_setter: Option<Address>,
+    pub protocol_fee_share_bps: u32,
+    /// Enabled fee tiers in bps, in the order they were added.
+    pub fee_tiers: Vec<u32>,
+    /// Who may call `create_pair`, and for which tokens.
+    pub creation_policy: CreationPolicy,
+}
+
+/// Restricts who may create pairs, on top of the token denylist.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CreationPolicy {
+    /// Anyone may create a pair for any tokens not on the denylist.
+    Open,
+    /// Only allowlisted creators may create pairs.
+    CreatorAllowlist,
+    /// Anyone may create pairs, but both tokens must be allowlisted.
+    TokenAllowlist,
+}
+
+/// Dynamic fee parameters pushed to pairs; mirrors `coralswap_pair::FeeParams`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeParams {
+    pub ema_alpha: i128,
+    pub baseline_fee_bps: u32,
+    pub min_fee_bps: u32,
+    pub max_fee_bps: u32,
+    pub ramp_up_multiplier: u32,
+    pub cooldown_divisor: u32,
+    pub decay_threshold_blocks: u64,
+}
+
+/// A privileged factory operation, executed once enough signers approve it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GovernanceAction {
+    Pause,
+    Unpause,
+    /// Enables a fee tier, or replaces its preset for future pairs.
+    SetFeeTier(u32, FeeParams),
+    SetPairFeeParams(Address, FeeParams),
+    SetPairPaused(Address, bool),
+    /// Replaces the signer set and threshold.
+    SetSigners(Vec<Address>, u32),
+    /// Starts the timelock for upgrading `UpgradeTarget` to a new WASM hash.
+    ProposeUpgrade(UpgradeTarget, BytesN<32>),
+    CancelUpgrade(UpgradeTarget),
+    SetUpgradeDelay(u64),
+    /// Proposes an account for a role; it takes effect once accepted.
+    ProposeRole(Role, Address),
+    RevokeRole(Role),
+    SetCreationPolicy(CreationPolicy),
+    SetCreatorAllowed(Address, bool),
+    SetTokenAllowed(Address, bool),
+    SetTokenDenied(Address, bool),
+}
+
+/// An administrative role, held by at most one account.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Grants and revokes the other roles, and transfers itself.
+    Admin,
+    /// Pauses the protocol instantly; unpausing still needs the signers.
+    Guardian,
+    /// Tunes the fee parameters of deployed pairs.
+    FeeManager,
+    /// Proposes and cancels timelocked upgrades.
+    Upgrader,
+}
+
+/// Which WASM a timelocked upgrade replaces.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeTarget {
+    /// The factory's own code, via `update_current_contract_wasm`.
+    Factory,
+    /// The code `create_pair` deploys for new pairs.
+    Pair,
+}
+
+/// A governance action awaiting (or having received) signer approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub action: GovernanceAction,
+    pub proposer: Address,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub executed: bool,
+    pub cancelled: bool,
 }
 
 #[contracttype]
 #[derive(Clone, Debug)]
 pub enum DataKey {
     Factory,
-    Pair(Address, Address),
+    /// Pair address by token pair and fee tier, keyed with the lower address
+    /// first.
+    Pair(Address, Address, u32),
+    /// Fee parameters applied to new pairs of this fee tier.
+    FeeTier(u32),
+    /// Current holder of a role.
+    RoleHolder(Role),
+    /// Proposed holder of a role, pending acceptance.
+    PendingRole(Role),
+    /// Present while the account is on the creator allowlist.
+    AllowedCreator(Address),
+    /// Present while the token is on the token allowlist.
+    AllowedToken(Address),
+    /// Present while the token is on the denylist.
+    DeniedToken(Address),
+    Proposal(u32),
+    PendingUpgrade(UpgradeTarget),
+    // Pair registry entries below live in persistent storage so factory call
+    // costs do not grow with the number of pairs.
+    /// Pair address by creation index.
+    PairAt(u32),
+    /// Pair code version the pair at this address runs.
+    PairCodeVersion(Address),
+    /// Pair containing this token by its position among the token's pairs,
+    /// in creation order.
+    TokenPairAt(Address, u32),
+    /// Number of pairs containing this token.
+    TokenPairCount(Address),
 }
 
 pub fn get_factory_storage(env: &Env) -> Option<FactoryStorage> {
@@ -29,31 +180,184 @@ pub fn get_factory_storage(env: &Env) -> Option<FactoryStorage> {
 
 pub fn set_factory_storage(env: &Env, storage: &FactoryStorage) {
     env.storage().instance().set(&DataKey::Factory, storage);
+    extend_instance_ttl(env);
+}
+
+/// Returns the pair for `(token_a, token_b)` in `fee_tier`, in either token
+/// order.
+pub fn get_pair(env: &Env, token_a: Address, token_b: Address, fee_tier: u32) -> Option<Address> {
+    get_persistent(env, &pair_key(token_a, token_b, fee_tier))
+}
+
+/// Stores the pair once, under the sorted token key.
+pub fn set_pair(env: &Env, token_a: Address, token_b: Address, fee_tier: u32, pair: Address) {
+    set_persistent(env, &pair_key(token_a, token_b, fee_tier), &pair);
+}
+
+/// Records a newly created pair in every lookup: by token pair and fee tier,
+/// by creation index, and under each of its tokens. Every lookup gets its own
+/// small entry, so a registration writes the same amount no matter how many
+/// pairs already exist.
+pub fn register_pair(
+    env: &Env,
+    index: u32,
+    token_0: &Address,
+    token_1: &Address,
+    fee_tier: u32,
+    pair: &Address,
+) {
+    set_pair(env, token_0.clone(), token_1.clone(), fee_tier, pair.clone());
+    set_pair_at(env, index, pair);
+    for token in [token_0, token_1] {
+        let count = get_token_pair_count(env, token);
+        set_persistent(env, &DataKey::TokenPairAt(token.clone(), count), pair);
+        set_persistent(env, &DataKey::TokenPairCount(token.clone()), &(count + 1));
+    }
+}
+
+pub fn get_token_pair_count(env: &Env, token: &Address) -> u32 {
+    get_persistent(env, &DataKey::TokenPairCount(token.clone())).unwrap_or(0)
 }
 
-pub fn get_pair(env: &Env, token_a: Address, token_b: Address) -> Option<Address> {
-    let key = DataKey::Pair(token_a, token_b);
-    env.storage().instance().get(&key)
+pub fn get_token_pair_at(env: &Env, token: &Address, index: u32) -> Option<Address> {
+    get_persistent(env, &DataKey::TokenPairAt(token.clone(), index))
 }
 
-pub fn set_pair(env: &Env, token_a: Address, token_b: Address, pair: Address) {
-    let key = DataKey::Pair(token_a, token_b);
-    env.storage().instance().set(&key, &pair);
+pub fn get_pair_at(env: &Env, index: u32) -> Option<Address> {
+    get_persistent(env, &DataKey::PairAt(index))
+}
+
+pub fn set_pair_at(env: &Env, index: u32, pair: &Address) {
+    set_persistent(env, &DataKey::PairAt(index), pair);
+}
+
+pub fn get_pair_code_version(env: &Env, pair: &Address) -> Option<u32> {
+    get_persistent(env, &DataKey::PairCodeVersion(pair.clone()))
+}
+
+pub fn set_pair_code_version(env: &Env, pair: &Address, version: u32) {
+    set_persistent(env, &DataKey::PairCodeVersion(pair.clone()), &version);
+}
+
+/// Whether a creation policy list entry exists, extending its TTL if so.
+pub fn has_list_entry(env: &Env, key: &DataKey) -> bool {
+    get_persistent::<bool>(env, key).is_some()
+}
+
+/// Adds or removes a creation policy list entry.
+pub fn set_list_entry(env: &Env, key: &DataKey, present: bool) {
+    if present {
+        set_persistent(env, key, &true);
+    } else {
+        env.storage().persistent().remove(key);
+    }
+}
+
+/// Canonical key for a token pair: the lower address always comes first, so
+/// each pair needs a single entry regardless of lookup order.
+pub fn pair_key(token_a: Address, token_b: Address, fee_tier: u32) -> DataKey {
+    if token_a < token_b {
+        DataKey::Pair(token_a, token_b, fee_tier)
+    } else {
+        DataKey::Pair(token_b, token_a, fee_tier)
+    }
+}
+
+pub fn get_fee_tier(env: &Env, fee_tier: u32) -> Option<FeeParams> {
+    env.storage().instance().get(&DataKey::FeeTier(fee_tier))
+}
+
+pub fn set_fee_tier(env: &Env, fee_tier: u32, params: &FeeParams) {
+    env.storage().instance().set(&DataKey::FeeTier(fee_tier), params);
+    extend_instance_ttl(env);
+}
+
+/// Reads a persistent entry, extending its TTL if it exists.
+fn get_persistent<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend_persistent_ttl(env, key);
+    }
+    value
+}
+
+/// Writes a persistent entry and extends its TTL.
+fn set_persistent<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    extend_persistent_ttl(env, key);
+}
+
+fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
+
+pub fn get_proposal(env: &Env, id: u32) -> Option<Proposal> {
+    get_persistent(env, &DataKey::Proposal(id))
+}
+
+pub fn set_proposal(env: &Env, id: u32, proposal: &Proposal) {
+    set_persistent(env, &DataKey::Proposal(id), proposal);
+}
+
+pub fn get_pending_upgrade(env: &Env, target: UpgradeTarget) -> Option<TimelockedAction> {
+    env.storage().instance().get(&DataKey::PendingUpgrade(target))
+}
+
+pub fn set_pending_upgrade(env: &Env, action: &TimelockedAction) {
+    env.storage().instance().set(&DataKey::PendingUpgrade(action.target), action);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_pending_upgrade(env: &Env, target: UpgradeTarget) {
+    env.storage().instance().remove(&DataKey::PendingUpgrade(target));
+}
+
+pub fn get_role_holder(env: &Env, role: Role) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RoleHolder(role))
+}
+
+pub fn set_role_holder(env: &Env, role: Role, account: &Address) {
+    env.storage().instance().set(&DataKey::RoleHolder(role), account);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_role_holder(env: &Env, role: Role) {
+    env.storage().instance().remove(&DataKey::RoleHolder(role));
+}
+
+pub fn get_pending_role(env: &Env, role: Role) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingRole(role))
+}
+
+pub fn set_pending_role(env: &Env, role: Role, account: &Address) {
+    env.storage().instance().set(&DataKey::PendingRole(role), account);
+    extend_instance_ttl(env);
+}
+
+pub fn remove_pending_role(env: &Env, role: Role) {
+    env.storage().instance().remove(&DataKey::PendingRole(role));
 }
 
 pub fn has_factory_storage(env: &Env) -> bool {
     env.storage().instance().has(&DataKey::Factory)
 }
 
-/// Extend instance storage TTL to keep contract alive.
+/// Extend instance storage TTL to keep contract alive. Called on every
+/// instance write, so any state-changing call keeps the factory live.
 pub fn extend_instance_ttl(env: &Env) {
     env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
 
+/// A proposed upgrade waiting out its timelock.
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TimelockedAction {
     pub proposed_at: u64,
     pub delay_seconds: u64,
-    pub action_id: u32,
+    pub target: UpgradeTarget,
+    pub new_wasm_hash: BytesN<32>,
 }
@@ -2,8 +2,79 @@ use soroban_sdk::Env;
 
 mod factory_tests {
     use super::*;
-    use crate::{Factory, FactoryClient};
-    use soroban_sdk::{testutils::Address as _, Address, Bytes, Vec};
+    use crate::{
+        errors::FactoryError,
+        fee_params::STANDARD_FEE_TIER,
+        storage::{
+            CreationPolicy, DataKey, FeeParams, GovernanceAction, Role, UpgradeTarget,
+            MAX_PROPOSAL_AGE,
+        },
+        Factory, FactoryClient,
+    };
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+        Address, Bytes, BytesN, String, Symbol, Vec,
+    };
+
+    /// Stand-in pair that records fee params pushed by the factory.
+    #[contract]
+    pub struct MockPair;
+
+    #[contractimpl]
+    impl MockPair {
+        pub fn set_fee_params(env: Env, fee_params: FeeParams) {
+            env.storage().instance().set(&symbol_short!("params"), &fee_params);
+        }
+
+        pub fn fee_params(env: Env) -> Option<FeeParams> {
+            env.storage().instance().get::<Symbol, FeeParams>(&symbol_short!("params"))
+        }
+
+        pub fn set_paused(env: Env, paused: bool) {
+            env.storage().instance().set(&symbol_short!("paused"), &paused);
+        }
+
+        pub fn is_paused(env: Env) -> bool {
+            env.storage().instance().get(&symbol_short!("paused")).unwrap_or(false)
+        }
+
+        pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+            env.storage().instance().set(&symbol_short!("wasm"), &new_wasm_hash);
+        }
+
+        pub fn migrate(env: Env) -> u32 {
+            let count: u32 = env.storage().instance().get(&symbol_short!("migrated")).unwrap_or(0);
+            env.storage().instance().set(&symbol_short!("migrated"), &(count + 1));
+            1
+        }
+
+        pub fn wasm(env: Env) -> Option<BytesN<32>> {
+            env.storage().instance().get(&symbol_short!("wasm"))
+        }
+
+        pub fn migrate_count(env: Env) -> u32 {
+            env.storage().instance().get(&symbol_short!("migrated")).unwrap_or(0)
+        }
+    }
+
+    /// Like `setup_env` but with `count` known governance signers and
+    /// the default majority threshold.
+    fn setup_with_signers<'a>(count: u32) -> (Env, FactoryClient<'a>, Vec<Address>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = FactoryClient::new(&env, &env.register_contract(None, Factory));
+        let mut signers = Vec::new(&env);
+        for _ in 0..count {
+            signers.push_back(Address::generate(&env));
+        }
+
+        let pair_wasm_hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+        let lp_token_wasm_hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+        client.initialize(&signers, &pair_wasm_hash, &lp_token_wasm_hash, &Address::generate(&env));
+
+        (env, client, signers)
+    }
 
     fn setup_env<'a>() -> (Env, FactoryClient<'a>, Address, Address, Address, Address) {
         let env = Env::default();
@@ -180,16 +251,1246 @@ mod factory_tests {
 
     #[test]
     fn test_create_pair_validation() {
-        let (_env, client, token_a, _token_b, _, _) = setup_env();
+        let (env, client, token_a, _token_b, _, _) = setup_env();
 
         // Identical tokens should return Err(IdenticalTokens = 8)
-        let result = client.try_create_pair(&token_a, &token_a);
+        let creator = Address::generate(&env);
+        let result = client.try_create_pair(&creator, &token_a, &token_a, &STANDARD_FEE_TIER);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_get_pair_none_for_missing() {
         let (_env, client, token_a, token_b, _, _) = setup_env();
-        assert!(client.get_pair(&token_a, &token_b).is_none());
+        assert!(client.get_pair(&token_a, &token_b, &STANDARD_FEE_TIER).is_none());
+    }
+
+    // ---------- Protocol fee share ----------
+
+    #[test]
+    fn test_protocol_fee_share_defaults_and_updates() {
+        let (env, client, _, _, _, fee_to_setter) = setup_env();
+        env.mock_all_auths();
+
+        assert_eq!(client.protocol_fee_share_bps(), 1_667);
+
+        client.set_protocol_fee_share(&fee_to_setter, &2_500);
+        assert_eq!(client.protocol_fee_share_bps(), 2_500);
+    }
+
+    #[test]
+    fn test_protocol_fee_share_rejects_out_of_bounds() {
+        let (env, client, _, _, _, fee_to_setter) = setup_env();
+        env.mock_all_auths();
+
+        let result = client.try_set_protocol_fee_share(&fee_to_setter, &0);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidProtocolFeeShare)));
+
+        let result = client.try_set_protocol_fee_share(&fee_to_setter, &5_001);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidProtocolFeeShare)));
+    }
+
+    #[test]
+    fn test_protocol_fee_share_requires_fee_to_setter() {
+        let (env, client, _, _, _, _) = setup_env();
+        env.mock_all_auths();
+
+        let stranger = Address::generate(&env);
+        let result = client.try_set_protocol_fee_share(&stranger, &2_500);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    // ---------- Dynamic fee params ----------
+
+    #[test]
+    fn test_fee_tiers_after_init() {
+        let (env, client, _) = setup_with_signers(1);
+        assert_eq!(client.fee_tiers(), Vec::from_array(&env, [1, 30, 100]));
+
+        let params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        assert_eq!(params.baseline_fee_bps, 30);
+        assert_eq!(params.min_fee_bps, 5);
+        assert_eq!(params.max_fee_bps, 100);
+
+        for fee_tier in [1, 100] {
+            let params = client.fee_tier_params(&fee_tier).unwrap();
+            assert_eq!(params.baseline_fee_bps, fee_tier);
+        }
+        assert_eq!(client.fee_tier_params(&5), None);
+    }
+
+    #[test]
+    fn test_set_fee_tier_updates_preset_by_signer() {
+        let (env, client, signers) = setup_with_signers(1);
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.min_fee_bps = 10;
+        client.set_fee_tier(&signers, &STANDARD_FEE_TIER, &params);
+
+        assert_eq!(client.fee_tier_params(&STANDARD_FEE_TIER).unwrap(), params);
+        assert_eq!(client.fee_tiers(), Vec::from_array(&env, [1, 30, 100]));
+    }
+
+    #[test]
+    fn test_set_fee_tier_enables_new_tier() {
+        let (env, client, signers) = setup_with_signers(1);
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.baseline_fee_bps = 5;
+        client.set_fee_tier(&signers, &5, &params);
+
+        assert_eq!(client.fee_tier_params(&5), Some(params));
+        assert_eq!(client.fee_tiers(), Vec::from_array(&env, [1, 30, 100, 5]));
+    }
+
+    #[test]
+    fn test_set_fee_tier_rejects_mismatched_baseline() {
+        let (_env, client, signers) = setup_with_signers(1);
+
+        let params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        let result = client.try_set_fee_tier(&signers, &5, &params);
+
+        assert_eq!(result, Err(Ok(FactoryError::InvalidFeeParams)));
+        assert_eq!(client.fee_tier_params(&5), None);
+    }
+
+    #[test]
+    fn test_set_fee_tier_caps_tier_count() {
+        let (_env, client, signers) = setup_with_signers(1);
+        let base = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+
+        for fee_tier in 40..47 {
+            let mut params = base.clone();
+            params.baseline_fee_bps = fee_tier;
+            client.set_fee_tier(&signers, &fee_tier, &params);
+        }
+        assert_eq!(client.fee_tiers().len(), 10);
+
+        let mut params = base.clone();
+        params.baseline_fee_bps = 50;
+        let result = client.try_set_fee_tier(&signers, &50, &params);
+        assert_eq!(result, Err(Ok(FactoryError::TooManyFeeTiers)));
+
+        // Existing tiers can still be updated at the cap
+        client.set_fee_tier(&signers, &STANDARD_FEE_TIER, &base);
+    }
+
+    #[test]
+    fn test_create_pair_rejects_unknown_fee_tier() {
+        let (env, client, _) = setup_with_signers(1);
+        let (token_a, token_b) = (Address::generate(&env), Address::generate(&env));
+
+        let result = client.try_create_pair(&Address::generate(&env), &token_a, &token_b, &5);
+        assert_eq!(result, Err(Ok(FactoryError::FeeTierNotEnabled)));
+    }
+
+    #[test]
+    fn test_set_fee_tier_rejects_non_signer() {
+        let (env, client, _) = setup_with_signers(1);
+
+        let params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        let result = client.try_set_fee_tier(
+            &Vec::from_array(&env, [Address::generate(&env)]),
+            &STANDARD_FEE_TIER,
+            &params,
+        );
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_set_fee_tier_rejects_out_of_bounds() {
+        let (_env, client, signers) = setup_with_signers(1);
+        let base = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+
+        let mut invalid = std::vec::Vec::new();
+        let mut p = base.clone();
+        p.baseline_fee_bps = p.max_fee_bps + 1;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.min_fee_bps = p.baseline_fee_bps + 1;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.max_fee_bps = 1_001;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.ema_alpha = 0;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.ramp_up_multiplier = 0;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.cooldown_divisor = 1;
+        invalid.push(p);
+        let mut p = base.clone();
+        p.decay_threshold_blocks = 0;
+        invalid.push(p);
+
+        for params in invalid {
+            let result = client.try_set_fee_tier(&signers, &STANDARD_FEE_TIER, &params);
+            assert_eq!(result, Err(Ok(FactoryError::InvalidFeeParams)));
+        }
+        assert_eq!(client.fee_tier_params(&STANDARD_FEE_TIER).unwrap(), base);
+    }
+
+    #[test]
+    fn test_set_pair_fee_params_pushes_to_pair() {
+        let (env, client, signers) = setup_with_signers(1);
+        let pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.max_fee_bps = 300;
+        client.set_pair_fee_params(&signers, &pair.address, &params);
+
+        assert_eq!(pair.fee_params(), Some(params));
+    }
+
+    #[test]
+    fn test_set_pair_fee_params_validates_before_calling_pair() {
+        let (env, client, signers) = setup_with_signers(1);
+        let pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.cooldown_divisor = 0;
+        let result = client.try_set_pair_fee_params(&signers, &pair.address, &params);
+
+        assert_eq!(result, Err(Ok(FactoryError::InvalidFeeParams)));
+        assert_eq!(pair.fee_params(), None);
+    }
+
+    // ── Pause ────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_set_pair_paused_toggles_only_that_pair() {
+        let (env, client, signers) = setup_with_signers(1);
+        let pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+        let other = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+
+        client.set_pair_paused(&signers, &pair.address, &true);
+        assert!(pair.is_paused());
+        assert!(!other.is_paused());
+        assert!(!client.is_paused());
+
+        client.set_pair_paused(&signers, &pair.address, &false);
+        assert!(!pair.is_paused());
+    }
+
+    #[test]
+    fn test_set_pair_paused_rejects_non_signer() {
+        let (env, client, _signers) = setup_with_signers(1);
+        let pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+
+        let result = client.try_set_pair_paused(
+            &Vec::from_array(&env, [Address::generate(&env)]),
+            &pair.address,
+            &true,
+        );
+
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        assert!(!pair.is_paused());
+    }
+
+    // ── Multisig governance ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_initialize_sets_majority_threshold() {
+        assert_eq!(setup_with_signers(1).1.threshold(), 1);
+        assert_eq!(setup_with_signers(3).1.threshold(), 2);
+        assert_eq!(setup_with_signers(10).1.threshold(), 6);
+    }
+
+    #[test]
+    fn test_initialize_rejects_duplicate_signers() {
+        let env = Env::default();
+        let client = FactoryClient::new(&env, &env.register_contract(None, Factory));
+        let signer = Address::generate(&env);
+        let hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+
+        let result = client.try_initialize(
+            &Vec::from_array(&env, [signer.clone(), signer]),
+            &hash,
+            &hash,
+            &Address::generate(&env),
+        );
+        assert_eq!(result, Err(Ok(FactoryError::DuplicateSigner)));
+    }
+
+    #[test]
+    fn test_pause_requires_threshold_of_signers() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1) = (signers.get_unchecked(0), signers.get_unchecked(1));
+
+        let result = client.try_pause(&Vec::from_array(&env, [s0.clone()]));
+        assert_eq!(result, Err(Ok(FactoryError::InsufficientSignatures)));
+
+        let result = client.try_pause(&Vec::from_array(&env, [s0.clone(), s0.clone()]));
+        assert_eq!(result, Err(Ok(FactoryError::DuplicateSigner)));
+
+        let result =
+            client.try_pause(&Vec::from_array(&env, [s0.clone(), Address::generate(&env)]));
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        assert!(!client.is_paused());
+
+        client.pause(&Vec::from_array(&env, [s0.clone(), s1.clone()]));
+        let authorizers: std::vec::Vec<Address> = env.auths().into_iter().map(|a| a.0).collect();
+        assert_eq!(authorizers, std::vec![s0.clone(), s1.clone()]);
+        assert!(client.is_paused());
+
+        client.unpause(&Vec::from_array(&env, [s1, s0]));
+        assert!(!client.is_paused());
+    }
+
+    #[test]
+    fn test_proposal_executes_once_threshold_reached() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1) = (signers.get_unchecked(0), signers.get_unchecked(1));
+
+        let id = client.propose(&s0, &GovernanceAction::Pause);
+        assert!(!client.is_paused());
+        let proposal = client.get_proposal(&id).unwrap();
+        assert_eq!(proposal.approvals, Vec::from_array(&env, [s0.clone()]));
+        assert!(!proposal.executed);
+
+        assert!(client.approve(&s1, &id));
+        assert!(client.is_paused());
+        assert!(client.get_proposal(&id).unwrap().executed);
+    }
+
+    #[test]
+    fn test_single_signer_proposal_executes_immediately() {
+        let (_env, client, signers) = setup_with_signers(1);
+
+        let id = client.propose(&signers.get_unchecked(0), &GovernanceAction::Pause);
+
+        assert!(client.is_paused());
+        assert!(client.get_proposal(&id).unwrap().executed);
+    }
+
+    #[test]
+    fn test_approve_rejects_repeat_executed_and_unknown() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1, s2) =
+            (signers.get_unchecked(0), signers.get_unchecked(1), signers.get_unchecked(2));
+
+        let id = client.propose(&s0, &GovernanceAction::Pause);
+        assert_eq!(client.try_approve(&s0, &id), Err(Ok(FactoryError::AlreadyApproved)));
+
+        client.approve(&s1, &id);
+        assert_eq!(client.try_approve(&s2, &id), Err(Ok(FactoryError::ProposalAlreadyExecuted)));
+        assert_eq!(client.try_approve(&s2, &(id + 1)), Err(Ok(FactoryError::ProposalNotFound)));
+        assert_eq!(
+            client.try_approve(&Address::generate(&env), &id),
+            Err(Ok(FactoryError::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_proposal_expires_after_max_age() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1) = (signers.get_unchecked(0), signers.get_unchecked(1));
+        env.ledger().set_timestamp(1_000);
+
+        let on_time =
+            client.propose(&s0, &GovernanceAction::SetCreationPolicy(CreationPolicy::Open));
+        let stale = client.propose(&s0, &GovernanceAction::Pause);
+
+        env.ledger().set_timestamp(1_000 + MAX_PROPOSAL_AGE);
+        assert!(client.approve(&s1, &on_time));
+
+        env.ledger().set_timestamp(1_000 + MAX_PROPOSAL_AGE + 1);
+        assert_eq!(client.try_approve(&s1, &stale), Err(Ok(FactoryError::ProposalExpired)));
+        assert!(!client.is_paused());
+        assert!(!client.get_proposal(&stale).unwrap().executed);
+    }
+
+    #[test]
+    fn test_proposer_can_cancel_pending_proposal() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1) = (signers.get_unchecked(0), signers.get_unchecked(1));
+
+        let id = client.propose(&s0, &GovernanceAction::Pause);
+        assert_eq!(client.try_cancel(&s1, &id), Err(Ok(FactoryError::Unauthorized)));
+        assert_eq!(
+            client.try_cancel(&Address::generate(&env), &id),
+            Err(Ok(FactoryError::Unauthorized))
+        );
+        assert_eq!(client.try_cancel(&s0, &(id + 1)), Err(Ok(FactoryError::ProposalNotFound)));
+
+        client.cancel(&s0, &id);
+        assert!(client.get_proposal(&id).unwrap().cancelled);
+        assert_eq!(client.try_approve(&s1, &id), Err(Ok(FactoryError::ProposalCancelled)));
+        assert_eq!(client.try_cancel(&s0, &id), Err(Ok(FactoryError::ProposalCancelled)));
+        assert!(!client.is_paused());
+
+        let executed = client.propose(&s0, &GovernanceAction::Pause);
+        client.approve(&s1, &executed);
+        assert_eq!(
+            client.try_cancel(&s0, &executed),
+            Err(Ok(FactoryError::ProposalAlreadyExecuted))
+        );
+    }
+
+    #[test]
+    fn test_propose_rejects_non_signer_and_invalid_action() {
+        let (env, client, signers) = setup_with_signers(3);
+
+        let result = client.try_propose(&Address::generate(&env), &GovernanceAction::Pause);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.max_fee_bps = 0;
+        let result = client.try_propose(
+            &signers.get_unchecked(0),
+            &GovernanceAction::SetFeeTier(STANDARD_FEE_TIER, params),
+        );
+        assert_eq!(result, Err(Ok(FactoryError::InvalidFeeParams)));
+
+        let action = GovernanceAction::SetSigners(signers.clone(), 4);
+        let result = client.try_propose(&signers.get_unchecked(0), &action);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidThreshold)));
+    }
+
+    #[test]
+    fn test_set_signers_rotates_set_and_threshold() {
+        let (env, client, signers) = setup_with_signers(3);
+        let new_signers = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+
+        client.set_signers(&signers, &new_signers, &1);
+        assert_eq!(client.signers(), new_signers);
+        assert_eq!(client.threshold(), 1);
+
+        let result = client.try_pause(&signers);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        client.pause(&Vec::from_array(&env, [new_signers.get_unchecked(1)]));
+        assert!(client.is_paused());
+    }
+
+    #[test]
+    fn test_set_signers_validates_new_set() {
+        let (env, client, signers) = setup_with_signers(3);
+        let a = Address::generate(&env);
+
+        let result = client.try_set_signers(&signers, &Vec::from_array(&env, [a.clone()]), &0);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidThreshold)));
+        let result = client.try_set_signers(&signers, &Vec::from_array(&env, [a.clone()]), &2);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidThreshold)));
+        let result = client.try_set_signers(&signers, &Vec::new(&env), &1);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidSignerCount)));
+        let result = client.try_set_signers(&signers, &Vec::from_array(&env, [a.clone(), a]), &1);
+        assert_eq!(result, Err(Ok(FactoryError::DuplicateSigner)));
+
+        let result = client.try_set_signers(
+            &Vec::from_array(&env, [signers.get_unchecked(0)]),
+            &signers,
+            &1,
+        );
+        assert_eq!(result, Err(Ok(FactoryError::InsufficientSignatures)));
+        assert_eq!(client.threshold(), 2);
+    }
+
+    #[test]
+    fn test_removed_signer_approvals_stop_counting() {
+        let (env, client, signers) = setup_with_signers(3);
+        let (s0, s1, s2) =
+            (signers.get_unchecked(0), signers.get_unchecked(1), signers.get_unchecked(2));
+
+        let id = client.propose(&s0, &GovernanceAction::Pause);
+        let rotated = Vec::from_array(&env, [s1.clone(), s2.clone(), Address::generate(&env)]);
+        client.set_signers(&Vec::from_array(&env, [s1.clone(), s2.clone()]), &rotated, &2);
+
+        assert!(!client.approve(&s1, &id));
+        assert!(!client.is_paused());
+        assert!(client.approve(&s2, &id));
+        assert!(client.is_paused());
+    }
+
+    // ── Timelocked upgrades ──────────────────────────────────────────────────
+
+    /// Pair upgrades only record the hash, so it need not be uploaded.
+    fn new_wasm_hash(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[7; 32])
+    }
+
+    #[test]
+    fn test_pair_upgrade_waits_for_timelock() {
+        let (env, client, signers) = setup_with_signers(3);
+        let hash = new_wasm_hash(&env);
+        env.ledger().set_timestamp(1_000);
+
+        client.propose_upgrade(&signers, &UpgradeTarget::Pair, &hash);
+        let pending = client.pending_upgrade(&UpgradeTarget::Pair).unwrap();
+        assert_eq!(pending.new_wasm_hash, hash);
+        assert_eq!(pending.delay_seconds, 259_200);
+
+        env.ledger().set_timestamp(1_000 + 259_199);
+        let result = client.try_execute_upgrade(&UpgradeTarget::Pair);
+        assert_eq!(result, Err(Ok(FactoryError::UpgradeTimelockNotExpired)));
+
+        env.ledger().set_timestamp(1_000 + 259_200);
+        assert_eq!(client.execute_upgrade(&UpgradeTarget::Pair), 2);
+        assert_eq!(client.protocol_version(), 2);
+        assert!(client.pending_upgrade(&UpgradeTarget::Pair).is_none());
+        env.as_contract(&client.address, || {
+            assert_eq!(crate::storage::get_factory_storage(&env).unwrap().pair_wasm_hash, hash);
+        });
+    }
+
+    #[test]
+    fn test_factory_upgrade_executes_after_timelock() {
+        let (env, client, signers) = setup_with_signers(1);
+        let hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+
+        client.propose_upgrade(&signers, &UpgradeTarget::Factory, &hash);
+        env.ledger().set_timestamp(259_200);
+
+        assert_eq!(client.execute_upgrade(&UpgradeTarget::Factory), 2);
+    }
+
+    #[test]
+    fn test_upgrade_proposal_requires_multisig() {
+        let (env, client, signers) = setup_with_signers(3);
+
+        let result = client.try_propose_upgrade(
+            &Vec::from_array(&env, [signers.get_unchecked(0)]),
+            &UpgradeTarget::Pair,
+            &new_wasm_hash(&env),
+        );
+        assert_eq!(result, Err(Ok(FactoryError::InsufficientSignatures)));
+        assert!(client.pending_upgrade(&UpgradeTarget::Pair).is_none());
+    }
+
+    #[test]
+    fn test_cancel_upgrade() {
+        let (env, client, signers) = setup_with_signers(3);
+        client.propose_upgrade(&signers, &UpgradeTarget::Pair, &new_wasm_hash(&env));
+
+        client.cancel_upgrade(&signers, &UpgradeTarget::Pair);
+        assert!(client.pending_upgrade(&UpgradeTarget::Pair).is_none());
+
+        env.ledger().set_timestamp(259_200);
+        let result = client.try_execute_upgrade(&UpgradeTarget::Pair);
+        assert_eq!(result, Err(Ok(FactoryError::NoPendingUpgrade)));
+        let result = client.try_cancel_upgrade(&signers, &UpgradeTarget::Pair);
+        assert_eq!(result, Err(Ok(FactoryError::NoPendingUpgrade)));
+        assert_eq!(client.protocol_version(), 1);
+    }
+
+    #[test]
+    fn test_upgrade_delay_is_bounded_and_captured_at_proposal() {
+        let (env, client, signers) = setup_with_signers(3);
+
+        let result = client.try_set_upgrade_delay(&signers, &86_399);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidUpgradeDelay)));
+        let result = client.try_set_upgrade_delay(&signers, &2_592_001);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidUpgradeDelay)));
+
+        client.propose_upgrade(&signers, &UpgradeTarget::Pair, &new_wasm_hash(&env));
+        client.set_upgrade_delay(&signers, &86_400);
+        assert_eq!(client.upgrade_delay(), 86_400);
+
+        env.ledger().set_timestamp(86_400);
+        let result = client.try_execute_upgrade(&UpgradeTarget::Pair);
+        assert_eq!(result, Err(Ok(FactoryError::UpgradeTimelockNotExpired)));
+    }
+
+    // ── Pair migration ───────────────────────────────────────────────────────
+
+    /// Registers `count` mock pairs as if `create_pair` had deployed them.
+    fn register_mock_pairs<'a>(
+        env: &Env,
+        client: &FactoryClient,
+        count: u32,
+    ) -> std::vec::Vec<MockPairClient<'a>> {
+        let pairs: std::vec::Vec<_> = (0..count)
+            .map(|_| MockPairClient::new(env, &env.register_contract(None, MockPair)))
+            .collect();
+        env.as_contract(&client.address, || {
+            let mut factory = crate::storage::get_factory_storage(env).unwrap();
+            for pair in &pairs {
+                let (token_0, token_1) = (Address::generate(env), Address::generate(env));
+                crate::storage::register_pair(
+                    env,
+                    factory.pair_count,
+                    &token_0,
+                    &token_1,
+                    STANDARD_FEE_TIER,
+                    &pair.address,
+                );
+                crate::storage::set_pair_code_version(env, &pair.address, 1);
+                factory.pair_count += 1;
+            }
+            crate::storage::set_factory_storage(env, &factory);
+        });
+        pairs
+    }
+
+    /// Proposes and executes a pair code upgrade to `new_wasm_hash(env)`.
+    fn upgrade_pair_code(env: &Env, client: &FactoryClient, signers: &Vec<Address>) {
+        client.propose_upgrade(signers, &UpgradeTarget::Pair, &new_wasm_hash(env));
+        env.ledger().set_timestamp(env.ledger().timestamp() + 259_200);
+        client.execute_upgrade(&UpgradeTarget::Pair);
+    }
+
+    #[test]
+    fn test_migrate_pairs_upgrades_stale_pairs() {
+        let (env, client, signers) = setup_with_signers(3);
+        let pairs = register_mock_pairs(&env, &client, 2);
+        assert_eq!(client.migrate_pairs(&0, &10), 0);
+
+        upgrade_pair_code(&env, &client, &signers);
+        assert_eq!(client.current_pair_code_version(), 2);
+
+        assert_eq!(client.migrate_pairs(&0, &10), 2);
+        for pair in &pairs {
+            assert_eq!(pair.wasm(), Some(new_wasm_hash(&env)));
+            assert_eq!(pair.migrate_count(), 1);
+            assert_eq!(client.pair_code_version(&pair.address), Some(2));
+        }
+
+        assert_eq!(client.migrate_pairs(&0, &10), 0);
+        assert_eq!(pairs[0].migrate_count(), 1);
+    }
+
+    #[test]
+    fn test_migrate_pairs_pages_through_range() {
+        let (env, client, signers) = setup_with_signers(3);
+        let pairs = register_mock_pairs(&env, &client, 3);
+        upgrade_pair_code(&env, &client, &signers);
+
+        assert_eq!(client.migrate_pairs(&0, &2), 2);
+        assert_eq!(client.pair_code_version(&pairs[2].address), Some(1));
+
+        assert_eq!(client.migrate_pairs(&2, &5), 1);
+        assert_eq!(client.pair_code_version(&pairs[2].address), Some(2));
+        assert_eq!(client.migrate_pairs(&3, &5), 0);
+    }
+
+    #[test]
+    fn test_pair_code_version_unknown_pair() {
+        let (env, client, _) = setup_with_signers(1);
+        assert_eq!(client.pair_code_version(&Address::generate(&env)), None);
+    }
+
+    // ── Pair registry ────────────────────────────────────────────────────────
+
+    /// Registers a standard tier pair for `(token_0, token_1)` as
+    /// `create_pair` would.
+    fn register_pair(
+        env: &Env,
+        client: &FactoryClient,
+        token_0: &Address,
+        token_1: &Address,
+    ) -> Address {
+        register_pair_in_tier(env, client, token_0, token_1, STANDARD_FEE_TIER)
+    }
+
+    fn register_pair_in_tier(
+        env: &Env,
+        client: &FactoryClient,
+        token_0: &Address,
+        token_1: &Address,
+        fee_tier: u32,
+    ) -> Address {
+        let pair = Address::generate(env);
+        crate::testutils::register_pair(env, &client.address, token_0, token_1, fee_tier, &pair);
+        pair
+    }
+
+    #[test]
+    fn test_all_pairs_by_creation_index() {
+        let (env, client, _) = setup_with_signers(1);
+        let tokens: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        let first = register_pair(&env, &client, &tokens[0], &tokens[1]);
+        let second = register_pair(&env, &client, &tokens[1], &tokens[2]);
+
+        assert_eq!(client.all_pairs_length(), 2);
+        assert_eq!(client.all_pairs(&0), Some(first.clone()));
+        assert_eq!(client.all_pairs(&1), Some(second));
+        assert_eq!(client.all_pairs(&2), None);
+        assert_eq!(client.get_pair(&tokens[1], &tokens[0], &STANDARD_FEE_TIER), Some(first));
+    }
+
+    #[test]
+    fn test_pairs_paged() {
+        let (env, client, _) = setup_with_signers(1);
+        let pairs: std::vec::Vec<Address> = (0..5)
+            .map(|_| {
+                register_pair(&env, &client, &Address::generate(&env), &Address::generate(&env))
+            })
+            .collect();
+
+        let page = client.pairs_paged(&1, &3);
+        assert_eq!(
+            page,
+            Vec::from_array(&env, [pairs[1].clone(), pairs[2].clone(), pairs[3].clone()])
+        );
+        assert_eq!(client.pairs_paged(&4, &10), Vec::from_array(&env, [pairs[4].clone()]));
+        assert_eq!(client.pairs_paged(&5, &10).len(), 0);
+        assert_eq!(client.pairs_paged(&0, &0).len(), 0);
+    }
+
+    #[test]
+    fn test_pairs_paged_caps_page_size() {
+        let (env, client, _) = setup_with_signers(1);
+        env.budget().reset_unlimited();
+        for _ in 0..101 {
+            register_pair(&env, &client, &Address::generate(&env), &Address::generate(&env));
+        }
+
+        assert_eq!(client.pairs_paged(&0, &u32::MAX).len(), 100);
+    }
+
+    #[test]
+    fn test_pairs_for_token() {
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y, z) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+        let xy = register_pair(&env, &client, &x, &y);
+        let yz = register_pair(&env, &client, &y, &z);
+
+        assert_eq!(client.pairs_for_token(&x, &0, &10), Vec::from_array(&env, [xy.clone()]));
+        assert_eq!(client.pairs_for_token(&y, &0, &10), Vec::from_array(&env, [xy, yz.clone()]));
+        assert_eq!(client.pairs_for_token(&z, &0, &10), Vec::from_array(&env, [yz]));
+        assert_eq!(client.pairs_for_token(&Address::generate(&env), &0, &10).len(), 0);
+        assert_eq!(client.pairs_for_token_length(&y), 2);
+    }
+
+    #[test]
+    fn test_pairs_for_token_paged() {
+        let (env, client, _) = setup_with_signers(1);
+        env.budget().reset_unlimited();
+        let hub = Address::generate(&env);
+        let pairs: std::vec::Vec<Address> = (0..101)
+            .map(|_| register_pair(&env, &client, &hub, &Address::generate(&env)))
+            .collect();
+
+        assert_eq!(client.pairs_for_token_length(&hub), 101);
+        assert_eq!(
+            client.pairs_for_token(&hub, &1, &2),
+            Vec::from_array(&env, [pairs[1].clone(), pairs[2].clone()])
+        );
+        assert_eq!(client.pairs_for_token(&hub, &0, &u32::MAX).len(), 100);
+        assert_eq!(
+            client.pairs_for_token(&hub, &100, &10),
+            Vec::from_array(&env, [pairs[100].clone()])
+        );
+        assert_eq!(client.pairs_for_token(&hub, &101, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_register_pair_cost_does_not_grow_with_token_pairs() {
+        let (env, client, _) = setup_with_signers(1);
+        env.budget().reset_unlimited();
+        let (hub, other) = (Address::generate(&env), Address::generate(&env));
+        register_pair(&env, &client, &other, &Address::generate(&env));
+        for _ in 0..200 {
+            register_pair(&env, &client, &hub, &Address::generate(&env));
+        }
+        let register_cost = |token: &Address| {
+            env.budget().reset_default();
+            register_pair(&env, &client, token, &Address::generate(&env));
+            env.budget().cpu_instruction_cost()
+        };
+
+        // A token with 200 pairs costs the same to extend as one with a single pair.
+        let hub_cost = register_cost(&hub);
+        let other_cost = register_cost(&other);
+        assert!(hub_cost.abs_diff(other_cost) < other_cost / 20, "{hub_cost} vs {other_cost}");
+    }
+
+    #[test]
+    fn test_pair_mapping_stored_once_in_persistent_storage() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let (low, high) = if x < y { (x.clone(), y.clone()) } else { (y.clone(), x.clone()) };
+        let pair = register_pair(&env, &client, &high, &low);
+
+        env.as_contract(&client.address, || {
+            let persistent = env.storage().persistent();
+            assert_eq!(
+                persistent.get(&DataKey::Pair(low.clone(), high.clone(), STANDARD_FEE_TIER)),
+                Some(pair.clone())
+            );
+            assert!(!persistent.has(&DataKey::Pair(high.clone(), low.clone(), STANDARD_FEE_TIER)));
+            assert!(!env.storage().instance().has(&DataKey::Pair(
+                low.clone(),
+                high.clone(),
+                STANDARD_FEE_TIER
+            )));
+            assert_eq!(
+                persistent.get_ttl(&DataKey::Pair(low.clone(), high.clone(), STANDARD_FEE_TIER)),
+                crate::storage::PERSISTENT_BUMP_AMOUNT
+            );
+        });
+        assert_eq!(client.get_pair(&x, &y, &STANDARD_FEE_TIER), Some(pair.clone()));
+        assert_eq!(client.get_pair(&y, &x, &STANDARD_FEE_TIER), Some(pair));
+    }
+
+    #[test]
+    fn test_pair_lookup_extends_ttl() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        register_pair(&env, &client, &x, &y);
+        let key = crate::storage::pair_key(x.clone(), y.clone(), STANDARD_FEE_TIER);
+
+        env.ledger()
+            .with_mut(|li| li.sequence_number += crate::storage::PERSISTENT_BUMP_AMOUNT - 10);
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().persistent().get_ttl(&key), 10);
+        });
+
+        assert!(client.get_pair(&y, &x, &STANDARD_FEE_TIER).is_some());
+        env.as_contract(&client.address, || {
+            assert_eq!(
+                env.storage().persistent().get_ttl(&key),
+                crate::storage::PERSISTENT_BUMP_AMOUNT
+            );
+        });
+    }
+
+    #[test]
+    fn test_governance_writes_extend_instance_ttl() {
+        use soroban_sdk::testutils::storage::Instance as _;
+
+        let (env, client, signers) = setup_with_signers(1);
+        env.ledger().with_mut(|li| li.sequence_number += crate::storage::INSTANCE_BUMP_AMOUNT - 10);
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().instance().get_ttl(), 10);
+        });
+
+        client.pause(&signers);
+
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().instance().get_ttl(), crate::storage::INSTANCE_BUMP_AMOUNT);
+        });
+    }
+
+    #[test]
+    fn test_bump_extends_instance_ttl() {
+        use soroban_sdk::testutils::storage::Instance as _;
+
+        let (env, client, _) = setup_with_signers(1);
+        env.ledger().with_mut(|li| li.sequence_number += crate::storage::INSTANCE_BUMP_AMOUNT - 10);
+
+        client.bump();
+
+        env.as_contract(&client.address, || {
+            assert_eq!(env.storage().instance().get_ttl(), crate::storage::INSTANCE_BUMP_AMOUNT);
+        });
+    }
+
+    #[test]
+    fn test_bump_requires_initialization() {
+        let env = Env::default();
+        let client = FactoryClient::new(&env, &env.register_contract(None, Factory));
+        assert_eq!(client.try_bump(), Err(Ok(FactoryError::NotInitialized)));
+    }
+
+    // ── LP token metadata ────────────────────────────────────────────────────
+
+    #[contract]
+    pub struct MockToken;
+
+    #[contractimpl]
+    impl MockToken {
+        pub fn set_symbol(env: Env, symbol: String) {
+            env.storage().instance().set(&symbol_short!("symbol"), &symbol);
+        }
+
+        pub fn symbol(env: Env) -> String {
+            env.storage().instance().get(&symbol_short!("symbol")).unwrap()
+        }
+    }
+
+    fn mock_token(env: &Env, symbol: &str) -> Address {
+        let token = env.register_contract(None, MockToken);
+        MockTokenClient::new(env, &token).set_symbol(&String::from_str(env, symbol));
+        token
+    }
+
+    fn lp_metadata(
+        env: &Env,
+        client: &FactoryClient,
+        a: &Address,
+        b: &Address,
+    ) -> (String, String) {
+        env.as_contract(&client.address, || crate::lp_metadata::lp_metadata(env, a, b))
+    }
+
+    /// Last five characters of `address`'s strkey.
+    fn strkey_tail(address: &Address) -> std::string::String {
+        let strkey = address.to_string().to_string();
+        strkey[strkey.len() - 5..].into()
+    }
+
+    #[test]
+    fn test_lp_metadata_uses_token_symbols() {
+        let (env, client, _) = setup_with_signers(1);
+        let usdc = mock_token(&env, "USDC");
+        let xlm = mock_token(&env, "XLM");
+
+        let (name, symbol) = lp_metadata(&env, &client, &usdc, &xlm);
+        assert_eq!(name, String::from_str(&env, "CORAL-LP USDC/XLM"));
+        assert_eq!(symbol, String::from_str(&env, "CLP-USDC-XLM"));
+    }
+
+    #[test]
+    fn test_lp_metadata_falls_back_to_address() {
+        let (env, client, _) = setup_with_signers(1);
+        let usdc = mock_token(&env, "USDC");
+        let no_contract = Address::generate(&env);
+
+        let (name, symbol) = lp_metadata(&env, &client, &usdc, &no_contract);
+        let tail = strkey_tail(&no_contract);
+        assert_eq!(name, String::from_str(&env, &std::format!("CORAL-LP USDC/{tail}")));
+        assert_eq!(symbol, String::from_str(&env, &std::format!("CLP-USDC-{tail}")));
+    }
+
+    #[test]
+    fn test_lp_metadata_rejects_unsuitable_symbols() {
+        let (env, client, _) = setup_with_signers(1);
+        let long = mock_token(&env, "THIRTEENCHARS");
+        let spaced = mock_token(&env, "A B");
+        let empty = mock_token(&env, "");
+
+        let (_, symbol) = lp_metadata(&env, &client, &long, &spaced);
+        let expected = std::format!("CLP-{}-{}", strkey_tail(&long), strkey_tail(&spaced));
+        assert_eq!(symbol, String::from_str(&env, &expected));
+
+        let (_, symbol) = lp_metadata(&env, &client, &empty, &mock_token(&env, "ABCDEFGHIJKL"));
+        let expected = std::format!("CLP-{}-ABCDEFGHIJKL", strkey_tail(&empty));
+        assert_eq!(symbol, String::from_str(&env, &expected));
+    }
+
+    #[test]
+    fn test_fee_tiers_keep_separate_pairs() {
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let stable = register_pair_in_tier(&env, &client, &x, &y, 1);
+        let standard = register_pair(&env, &client, &x, &y);
+
+        assert_ne!(stable, standard);
+        assert_eq!(client.get_pair(&y, &x, &1), Some(stable.clone()));
+        assert_eq!(client.get_pair(&y, &x, &STANDARD_FEE_TIER), Some(standard.clone()));
+        assert_eq!(client.get_pair(&x, &y, &100), None);
+        assert_eq!(client.pairs_for_token(&x, &0, &10), Vec::from_array(&env, [stable, standard]));
+    }
+
+    // ── Roles ────────────────────────────────────────────────────────────────
+
+    /// Has the signers propose a fresh account for `role`, which accepts it.
+    fn grant_role(
+        env: &Env,
+        client: &FactoryClient,
+        signers: &Vec<Address>,
+        role: Role,
+    ) -> Address {
+        let account = Address::generate(env);
+        let action = GovernanceAction::ProposeRole(role, account.clone());
+        let id = client.propose(&signers.get_unchecked(0), &action);
+        for signer in signers.iter().skip(1) {
+            if client.get_proposal(&id).unwrap().executed {
+                break;
+            }
+            client.approve(&signer, &id);
+        }
+        client.accept_role(&account, &role);
+        account
+    }
+
+    #[test]
+    fn test_role_takes_effect_on_acceptance() {
+        let (env, client, signers) = setup_with_signers(1);
+        let admin = Address::generate(&env);
+
+        let action = GovernanceAction::ProposeRole(Role::Admin, admin.clone());
+        client.propose(&signers.get_unchecked(0), &action);
+        assert_eq!(client.pending_role(&Role::Admin), Some(admin.clone()));
+        assert_eq!(client.role(&Role::Admin), None);
+
+        let result = client.try_accept_role(&Address::generate(&env), &Role::Admin);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        client.accept_role(&admin, &Role::Admin);
+        assert_eq!(
+            env.auths()[0].0,
+            admin.clone(),
+            "accept_role must be authorized by the proposed account"
+        );
+        assert_eq!(client.role(&Role::Admin), Some(admin));
+        assert_eq!(client.pending_role(&Role::Admin), None);
+
+        let result = client.try_accept_role(&Address::generate(&env), &Role::Guardian);
+        assert_eq!(result, Err(Ok(FactoryError::NoPendingTransfer)));
+    }
+
+    #[test]
+    fn test_admin_grants_and_revokes_roles() {
+        let (env, client, signers) = setup_with_signers(1);
+        let admin = grant_role(&env, &client, &signers, Role::Admin);
+        let guardian = Address::generate(&env);
+
+        client.propose_role(&admin, &Role::Guardian, &guardian);
+        client.accept_role(&guardian, &Role::Guardian);
+        assert_eq!(client.role(&Role::Guardian), Some(guardian.clone()));
+
+        client.revoke_role(&admin, &Role::Guardian);
+        assert_eq!(client.role(&Role::Guardian), None);
+        let result = client.try_guardian_pause(&guardian);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        // Only the admin manages roles
+        let result = client.try_propose_role(&guardian, &Role::Guardian, &guardian);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        let result = client.try_revoke_role(&guardian, &Role::Admin);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_admin_role_transfer_is_two_step() {
+        let (env, client, signers) = setup_with_signers(1);
+        let admin = grant_role(&env, &client, &signers, Role::Admin);
+        let next_admin = Address::generate(&env);
+
+        client.propose_role(&admin, &Role::Admin, &next_admin);
+        assert_eq!(client.role(&Role::Admin), Some(admin.clone()));
+
+        client.accept_role(&next_admin, &Role::Admin);
+        assert_eq!(client.role(&Role::Admin), Some(next_admin));
+        let result = client.try_revoke_role(&admin, &Role::Guardian);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_guardian_pauses_but_cannot_unpause() {
+        let (env, client, signers) = setup_with_signers(3);
+        let guardian = grant_role(&env, &client, &signers, Role::Guardian);
+
+        let result = client.try_guardian_pause(&Address::generate(&env));
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        client.guardian_pause(&guardian);
+        assert!(client.is_paused());
+
+        let result = client.try_unpause(&Vec::from_array(&env, [guardian]));
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_fee_manager_tunes_pair_fee_params() {
+        let (env, client, signers) = setup_with_signers(1);
+        let fee_manager = grant_role(&env, &client, &signers, Role::FeeManager);
+        let pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+
+        let mut params = client.fee_tier_params(&STANDARD_FEE_TIER).unwrap();
+        params.max_fee_bps = 300;
+        client.fee_manager_set_pair_fee_params(&fee_manager, &pair.address, &params);
+        assert_eq!(pair.fee_params(), Some(params.clone()));
+
+        let result = client.try_fee_manager_set_pair_fee_params(
+            &signers.get_unchecked(0),
+            &pair.address,
+            &params,
+        );
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        params.cooldown_divisor = 0;
+        let result =
+            client.try_fee_manager_set_pair_fee_params(&fee_manager, &pair.address, &params);
+        assert_eq!(result, Err(Ok(FactoryError::InvalidFeeParams)));
+    }
+
+    #[test]
+    fn test_upgrader_proposes_through_timelock() {
+        let (env, client, signers) = setup_with_signers(1);
+        let upgrader = grant_role(&env, &client, &signers, Role::Upgrader);
+        let hash = new_wasm_hash(&env);
+        env.ledger().set_timestamp(1_000);
+
+        client.upgrader_propose_upgrade(&upgrader, &UpgradeTarget::Pair, &hash);
+        let result = client.try_execute_upgrade(&UpgradeTarget::Pair);
+        assert_eq!(result, Err(Ok(FactoryError::UpgradeTimelockNotExpired)));
+
+        client.upgrader_cancel_upgrade(&upgrader, &UpgradeTarget::Pair);
+        assert!(client.pending_upgrade(&UpgradeTarget::Pair).is_none());
+
+        let result = client.try_upgrader_propose_upgrade(
+            &Address::generate(&env),
+            &UpgradeTarget::Pair,
+            &hash,
+        );
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_fee_to_setter_transfer_is_two_step() {
+        let (env, client, _, _, _, fee_to_setter) = setup_env();
+        env.mock_all_auths();
+        let new_setter = Address::generate(&env);
+
+        client.set_fee_to_setter(&fee_to_setter, &new_setter);
+        assert_eq!(client.fee_to_setter(), Some(fee_to_setter.clone()));
+        assert_eq!(client.pending_fee_to_setter(), Some(new_setter.clone()));
+
+        let result = client.try_accept_fee_to_setter(&Address::generate(&env));
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+
+        client.accept_fee_to_setter(&new_setter);
+        assert_eq!(client.fee_to_setter(), Some(new_setter.clone()));
+        assert_eq!(client.pending_fee_to_setter(), None);
+
+        let result = client.try_set_fee_to(&fee_to_setter, &None);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        let result = client.try_accept_fee_to_setter(&new_setter);
+        assert_eq!(result, Err(Ok(FactoryError::NoPendingTransfer)));
+    }
+
+    // ── Creation policy ──────────────────────────────────────────────────────
+
+    /// Runs the creation policy check `create_pair` applies before deploying.
+    fn check_create(
+        env: &Env,
+        client: &FactoryClient,
+        creator: &Address,
+        token_a: &Address,
+        token_b: &Address,
+    ) -> Result<(), FactoryError> {
+        env.as_contract(&client.address, || {
+            let policy = crate::storage::get_factory_storage(env).unwrap().creation_policy;
+            crate::policy::check_create(env, policy, creator, token_a, token_b)
+        })
+    }
+
+    #[test]
+    fn test_creation_policy_defaults_to_open() {
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+
+        assert_eq!(client.creation_policy(), CreationPolicy::Open);
+        assert_eq!(check_create(&env, &client, &Address::generate(&env), &x, &y), Ok(()));
+    }
+
+    #[test]
+    fn test_denylisted_token_rejected_under_every_policy() {
+        let (env, client, signers) = setup_with_signers(1);
+        let (scam, y) = (Address::generate(&env), Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        client.set_token_denied(&signers, &scam, &true);
+        assert!(client.is_token_denied(&scam));
+
+        let result = client.try_create_pair(&creator, &scam, &y, &STANDARD_FEE_TIER);
+        assert_eq!(result, Err(Ok(FactoryError::TokenDenied)));
+
+        client.set_creator_allowed(&signers, &creator, &true);
+        client.set_token_allowed(&signers, &scam, &true);
+        client.set_token_allowed(&signers, &y, &true);
+        for policy in [CreationPolicy::CreatorAllowlist, CreationPolicy::TokenAllowlist] {
+            client.set_creation_policy(&signers, &policy);
+            let result = client.try_create_pair(&creator, &y, &scam, &STANDARD_FEE_TIER);
+            assert_eq!(result, Err(Ok(FactoryError::TokenDenied)));
+        }
+
+        client.set_token_denied(&signers, &scam, &false);
+        assert!(!client.is_token_denied(&scam));
+        assert_eq!(check_create(&env, &client, &creator, &scam, &y), Ok(()));
+    }
+
+    #[test]
+    fn test_creator_allowlist_policy() {
+        let (env, client, signers) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        client.set_creation_policy(&signers, &CreationPolicy::CreatorAllowlist);
+        assert_eq!(client.creation_policy(), CreationPolicy::CreatorAllowlist);
+
+        let result = client.try_create_pair(&creator, &x, &y, &STANDARD_FEE_TIER);
+        assert_eq!(result, Err(Ok(FactoryError::CreatorNotAllowed)));
+
+        client.set_creator_allowed(&signers, &creator, &true);
+        assert!(client.is_creator_allowed(&creator));
+        assert_eq!(check_create(&env, &client, &creator, &x, &y), Ok(()));
+
+        client.set_creator_allowed(&signers, &creator, &false);
+        assert_eq!(
+            check_create(&env, &client, &creator, &x, &y),
+            Err(FactoryError::CreatorNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_token_allowlist_policy() {
+        let (env, client, signers) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        client.set_creation_policy(&signers, &CreationPolicy::TokenAllowlist);
+        client.set_token_allowed(&signers, &x, &true);
+        assert!(client.is_token_allowed(&x));
+
+        let result = client.try_create_pair(&creator, &x, &y, &STANDARD_FEE_TIER);
+        assert_eq!(result, Err(Ok(FactoryError::TokenNotAllowed)));
+
+        client.set_token_allowed(&signers, &y, &true);
+        assert_eq!(check_create(&env, &client, &creator, &x, &y), Ok(()));
+    }
+
+    #[test]
+    fn test_creation_policy_updates_require_signers() {
+        let (env, client, _) = setup_with_signers(1);
+        let outsider = Vec::from_array(&env, [Address::generate(&env)]);
+        let token = Address::generate(&env);
+
+        let result = client.try_set_creation_policy(&outsider, &CreationPolicy::TokenAllowlist);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        let result = client.try_set_token_denied(&outsider, &token, &true);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        let result = client.try_set_creator_allowed(&outsider, &token, &true);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        let result = client.try_set_token_allowed(&outsider, &token, &true);
+        assert_eq!(result, Err(Ok(FactoryError::Unauthorized)));
+        assert_eq!(client.creation_policy(), CreationPolicy::Open);
+    }
+
+    // ── Seeded pair creation ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_create_pair_and_seed_rejects_non_positive_amounts() {
+        let (env, client, _) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        for (amount_x, amount_y) in [(0, 1_000), (1_000, 0), (-1, 1_000)] {
+            let result = client.try_create_pair_and_seed(
+                &creator,
+                &x,
+                &y,
+                &STANDARD_FEE_TIER,
+                &amount_x,
+                &amount_y,
+            );
+            assert_eq!(result, Err(Ok(FactoryError::InvalidAmount)));
+        }
+    }
+
+    #[test]
+    fn test_create_pair_and_seed_applies_creation_policy() {
+        let (env, client, signers) = setup_with_signers(1);
+        let (x, y) = (Address::generate(&env), Address::generate(&env));
+        let creator = Address::generate(&env);
+
+        client.set_token_denied(&signers, &x, &true);
+        let result =
+            client.try_create_pair_and_seed(&creator, &x, &y, &STANDARD_FEE_TIER, &1_000, &1_000);
+        assert_eq!(result, Err(Ok(FactoryError::TokenDenied)));
+
+        client.set_token_denied(&signers, &x, &false);
+        client.set_creation_policy(&signers, &CreationPolicy::CreatorAllowlist);
+        let result =
+            client.try_create_pair_and_seed(&creator, &x, &y, &STANDARD_FEE_TIER, &1_000, &1_000);
+        assert_eq!(result, Err(Ok(FactoryError::CreatorNotAllowed)));
     }
 }
@@ -0,0 +1,51 @@
+//! Helpers for tests that drive the factory alongside natively registered
+//! contracts. Enabled by the `testutils` feature.
+
+use soroban_sdk::{Address, Env};
+
+use crate::{storage, Factory};
+
+/// Records `pair` as the factory's pair for `(token_a, token_b, fee_tier)`,
+/// as `create_pair` does once it has deployed and initialized the pair.
+///
+/// Native tests cannot deploy a pair from its wasm hash, so they register a
+/// `Pair` contract themselves and hand it to the factory through this.
+pub fn register_pair(
+    env: &Env,
+    factory: &Address,
+    token_a: &Address,
+    token_b: &Address,
+    fee_tier: u32,
+    pair: &Address,
+) {
+    let (token_0, token_1) =
+        if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+    env.as_contract(factory, || {
+        let mut factory_storage =
+            storage::get_factory_storage(env).expect("factory not initialized");
+        storage::register_pair(env, factory_storage.pair_count, token_0, token_1, fee_tier, pair);
+        storage::set_pair_code_version(env, pair, factory_storage.pair_code_version);
+        factory_storage.pair_count += 1;
+        storage::set_factory_storage(env, &factory_storage);
+    });
+}
+
+/// Initializes natively registered `pair` and `lp_token` contracts and
+/// registers them for `(token_a, token_b, fee_tier)`, exactly as
+/// `create_pair` does after deploying them from wasm.
+pub fn install_pair(
+    env: &Env,
+    factory: &Address,
+    token_a: &Address,
+    token_b: &Address,
+    fee_tier: u32,
+    pair: &Address,
+    lp_token: &Address,
+) {
+    let (token_0, token_1) =
+        if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+    env.as_contract(factory, || {
+        Factory::install_pair(env, token_0, token_1, fee_tier, pair, lp_token)
+            .expect("pair installation failed");
+    });
+}
@@ -1,14 +1,80 @@
 use crate::errors::FactoryError;
+use crate::events::FactoryEvents;
+use crate::storage::{self, TimelockedAction, UpgradeTarget, MAX_UPGRADE_DELAY, MIN_UPGRADE_DELAY};
 use soroban_sdk::{BytesN, Env};
 
-/// Proposed a timelocked contract upgrade (72h delay).
-#[allow(dead_code)]
-pub fn propose_upgrade(_env: &Env, _new_wasm_hash: BytesN<32>) -> Result<(), FactoryError> {
-    todo!()
+/// Checks a proposed upgrade delay against `MIN_UPGRADE_DELAY..=MAX_UPGRADE_DELAY`.
+pub fn validate_delay(delay_seconds: u64) -> Result<(), FactoryError> {
+    if !(MIN_UPGRADE_DELAY..=MAX_UPGRADE_DELAY).contains(&delay_seconds) {
+        return Err(FactoryError::InvalidUpgradeDelay);
+    }
+    Ok(())
+}
+
+/// Proposed a timelocked upgrade of `target` to `new_wasm_hash`.
+///
+/// The factory's current `upgrade_delay` is captured at proposal time, so
+/// later delay changes do not shorten an upgrade already in flight. A new
+/// proposal for the same target replaces the pending one and restarts the clock.
+pub fn propose_upgrade(
+    env: &Env,
+    target: UpgradeTarget,
+    new_wasm_hash: BytesN<32>,
+) -> Result<(), FactoryError> {
+    let factory = storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+
+    storage::set_pending_upgrade(
+        env,
+        &TimelockedAction {
+            proposed_at: env.ledger().timestamp(),
+            delay_seconds: factory.upgrade_delay,
+            target,
+            new_wasm_hash: new_wasm_hash.clone(),
+        },
+    );
+    FactoryEvents::upgrade_proposed(env, &new_wasm_hash.to_array());
+    Ok(())
+}
+
+/// Cancelled the pending upgrade of `target`.
+pub fn cancel_upgrade(env: &Env, target: UpgradeTarget) -> Result<(), FactoryError> {
+    if storage::get_pending_upgrade(env, target).is_none() {
+        return Err(FactoryError::NoPendingUpgrade);
+    }
+    storage::remove_pending_upgrade(env, target);
+    FactoryEvents::upgrade_cancelled(env, target);
+    Ok(())
 }
 
 /// Executed a previously proposed upgrade after timelock expiry.
-#[allow(dead_code)]
-pub fn execute_upgrade(_env: &Env) -> Result<(), FactoryError> {
-    todo!()
+///
+/// Factory upgrades swap the running code in place; pair upgrades change the
+/// WASM that `create_pair` deploys and bump `pair_code_version`, after which
+/// existing pairs are brought over with `migrate_pairs`. Either way
+/// `protocol_version` is bumped.
+///
+/// # Returns
+/// The new `protocol_version`.
+pub fn execute_upgrade(env: &Env, target: UpgradeTarget) -> Result<u32, FactoryError> {
+    let pending =
+        storage::get_pending_upgrade(env, target).ok_or(FactoryError::NoPendingUpgrade)?;
+    let ready_at = pending.proposed_at.saturating_add(pending.delay_seconds);
+    if env.ledger().timestamp() < ready_at {
+        return Err(FactoryError::UpgradeTimelockNotExpired);
+    }
+
+    let mut factory = storage::get_factory_storage(env).ok_or(FactoryError::NotInitialized)?;
+    factory.protocol_version += 1;
+    if target == UpgradeTarget::Pair {
+        factory.pair_wasm_hash = pending.new_wasm_hash.clone();
+        factory.pair_code_version += 1;
+    }
+    storage::set_factory_storage(env, &factory);
+    storage::remove_pending_upgrade(env, target);
+
+    if target == UpgradeTarget::Factory {
+        env.deployer().update_current_contract_wasm(pending.new_wasm_hash);
+    }
+    FactoryEvents::upgrade_executed(env, factory.protocol_version);
+    Ok(factory.protocol_version)
 }
@@ -0,0 +1,188 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::LpTokenError;
+use crate::storage::{
+    read_checkpoint, read_checkpoint_head, retain_checkpoint, write_checkpoint,
+    write_checkpoint_head, Checkpoint, CheckpointHead, LpTokenKey, CHECKPOINT_WINDOW,
+};
+
+// Historical balance checkpoints.
+//
+// Every balance, total supply and voting power change appends `(ledger, new_amount)` to that
+// balance's history, so governance can weight votes by LP holdings at a past
+// ledger. Each checkpoint is its own persistent entry next to a small head, so
+// recording one touches a few small entries however long the history grows.
+// Repeated changes within one ledger overwrite that ledger's checkpoint.
+//
+// Lookups reach back `CHECKPOINT_WINDOW` ledgers, half the TTL bump. A
+// checkpoint that gets superseded is extended by the full bump, so every
+// checkpoint a lookup inside the window can land on is still live. Older
+// checkpoints are never read again and lapse into the archive, which keeps
+// live storage bounded; the head tracks the first one still in reach, and
+// lookups before the window fail with `LedgerTooOld`. A history left untouched
+// for a whole bump period archives along with the balance it tracks, and like
+// that balance must be restored before it can be read again.
+
+/// One balance's checkpoint history.
+enum Series<'a> {
+    Balance(&'a Address),
+    Supply,
+    Votes(&'a Address),
+}
+
+impl Series<'_> {
+    fn head_key(&self) -> LpTokenKey {
+        match self {
+            Series::Balance(id) => LpTokenKey::BalanceCheckpointHead((*id).clone()),
+            Series::Supply => LpTokenKey::SupplyCheckpointHead,
+            Series::Votes(delegate) => LpTokenKey::VoteCheckpointHead((*delegate).clone()),
+        }
+    }
+
+    fn checkpoint_key(&self, index: u32) -> LpTokenKey {
+        match self {
+            Series::Balance(id) => LpTokenKey::BalanceCheckpoint((*id).clone(), index),
+            Series::Supply => LpTokenKey::SupplyCheckpoint(index),
+            Series::Votes(delegate) => LpTokenKey::VoteCheckpoint((*delegate).clone(), index),
+        }
+    }
+
+    fn checkpoint(&self, env: &Env, index: u32) -> Checkpoint {
+        read_checkpoint(env, &self.checkpoint_key(index)).expect("checkpoint below count")
+    }
+}
+
+/// Records `balance` as `id`'s balance as of the current ledger.
+pub fn record_balance(env: &Env, id: &Address, balance: i128) {
+    record(env, &Series::Balance(id), balance);
+}
+
+/// Records `total_supply` as the total supply as of the current ledger.
+pub fn record_total_supply(env: &Env, total_supply: i128) {
+    record(env, &Series::Supply, total_supply);
+}
+
+/// Records `votes` as `delegate`'s voting power as of the current ledger.
+pub fn record_votes(env: &Env, delegate: &Address, votes: i128) {
+    record(env, &Series::Votes(delegate), votes);
+}
+
+/// Returns `delegate`'s current voting power.
+pub fn current_votes(env: &Env, delegate: &Address) -> i128 {
+    let series = Series::Votes(delegate);
+    match read_checkpoint_head(env, &series.head_key()).count {
+        0 => 0,
+        count => series.checkpoint(env, count - 1).amount,
+    }
+}
+
+/// Returns `delegate`'s voting power at the end of `ledger`.
+pub fn votes_at(env: &Env, delegate: &Address, ledger: u32) -> Result<i128, LpTokenError> {
+    lookup(env, &Series::Votes(delegate), ledger)
+}
+
+/// Returns `id`'s balance at the end of `ledger`.
+pub fn balance_at(env: &Env, id: &Address, ledger: u32) -> Result<i128, LpTokenError> {
+    lookup(env, &Series::Balance(id), ledger)
+}
+
+/// Returns the total supply at the end of `ledger`.
+pub fn total_supply_at(env: &Env, ledger: u32) -> Result<i128, LpTokenError> {
+    lookup(env, &Series::Supply, ledger)
+}
+
+fn record(env: &Env, series: &Series, amount: i128) {
+    let ledger = env.ledger().sequence();
+    let head_key = series.head_key();
+    let mut head = read_checkpoint_head(env, &head_key);
+
+    let checkpoint = Checkpoint { ledger, amount };
+    if head.count > 0 {
+        let latest_key = series.checkpoint_key(head.count - 1);
+        let latest = series.checkpoint(env, head.count - 1);
+        if latest.ledger == ledger {
+            write_checkpoint(env, &latest_key, &checkpoint);
+            return;
+        }
+        retain_checkpoint(env, &latest_key);
+        head.first = first_in_window(env, series, &head, &latest, ledger);
+    }
+    write_checkpoint(env, &series.checkpoint_key(head.count), &checkpoint);
+    head.count += 1;
+    write_checkpoint_head(env, &head_key, &head);
+}
+
+/// Finds the last checkpoint at or before the start of the window as of
+/// `ledger`, the oldest one any later lookup can land on.
+fn first_in_window(
+    env: &Env,
+    series: &Series,
+    head: &CheckpointHead,
+    latest: &Checkpoint,
+    ledger: u32,
+) -> u32 {
+    let start = ledger.saturating_sub(CHECKPOINT_WINDOW);
+    if latest.ledger <= start {
+        return head.count - 1;
+    }
+
+    // Count the checkpoints at or before `start`, from the previous first on.
+    let (mut low, mut high) = (head.first, head.count - 1);
+    while low < high {
+        let mid = (low + high) / 2;
+        if series.checkpoint(env, mid).ledger <= start {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == head.first {
+        // Still short of the window; nothing has fallen out of reach.
+        return head.first;
+    }
+    low - 1
+}
+
+/// Binary-searches for the last checkpoint at or before `ledger`.
+///
+/// Only ledgers that have closed can be queried, since balances in the
+/// current ledger may still change, and only within the last
+/// `CHECKPOINT_WINDOW` ledgers. The latest checkpoint is tried first, as most
+/// lookups ask about recent ledgers.
+fn lookup(env: &Env, series: &Series, ledger: u32) -> Result<i128, LpTokenError> {
+    let now = env.ledger().sequence();
+    if ledger >= now {
+        return Err(LpTokenError::LedgerNotPast);
+    }
+    if ledger < now.saturating_sub(CHECKPOINT_WINDOW) {
+        return Err(LpTokenError::LedgerTooOld);
+    }
+
+    let head = read_checkpoint_head(env, &series.head_key());
+    if head.count == 0 {
+        return Ok(0);
+    }
+    let latest = series.checkpoint(env, head.count - 1);
+    if latest.ledger <= ledger {
+        return Ok(latest.amount);
+    }
+
+    // Count the checkpoints at or before `ledger`. Those up to `first` all
+    // are, as `first` is at or before the start of the window.
+    let (mut low, mut high) = (head.first, head.count - 1);
+    while low < high {
+        let mid = (low + high) / 2;
+        if series.checkpoint(env, mid).ledger <= ledger {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    if low == 0 {
+        // Before the first checkpoint, the balance was zero.
+        return Ok(0);
+    }
+    Ok(series.checkpoint(env, low - 1).amount)
+}
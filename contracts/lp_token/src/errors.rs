@@ -10,4 +10,9 @@ pub enum LpTokenError {
     InsufficientBalance = 203,
     InsufficientAllowance = 204,
     Overflow = 205,
+    NegativeAmount = 206,
+    InvalidExpiration = 207,
+    InvalidPermitSigner = 208,
+    LedgerNotPast = 209,
+    LedgerTooOld = 210,
 }
@@ -0,0 +1,68 @@
+use soroban_sdk::{symbol_short, Address, Env};
+
+/// SEP-41 token events, with the same topics and data as
+/// `soroban_token_sdk::events::Events` so indexers treat LP tokens like any
+/// other Stellar token, plus the vote delegation events.
+pub struct LpTokenEvents;
+
+impl LpTokenEvents {
+    /// Topics: `("approve", from, spender)`
+    /// Data:   `(amount, expiration_ledger)`
+    pub fn approve(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        env.events().publish(
+            (symbol_short!("approve"), from.clone(), spender.clone()),
+            (amount, expiration_ledger),
+        );
+    }
+
+    /// Topics: `("transfer", from, to)`
+    /// Data:   `amount`
+    pub fn transfer(env: &Env, from: &Address, to: &Address, amount: i128) {
+        env.events().publish((symbol_short!("transfer"), from.clone(), to.clone()), amount);
+    }
+
+    /// Topics: `("mint", admin, to)`
+    /// Data:   `amount`
+    pub fn mint(env: &Env, admin: &Address, to: &Address, amount: i128) {
+        env.events().publish((symbol_short!("mint"), admin.clone(), to.clone()), amount);
+    }
+
+    /// Topics: `("burn", from)`
+    /// Data:   `amount`
+    pub fn burn(env: &Env, from: &Address, amount: i128) {
+        env.events().publish((symbol_short!("burn"), from.clone()), amount);
+    }
+
+    /// Topics: `("set_admin", admin)`
+    /// Data:   `new_admin`
+    pub fn set_admin(env: &Env, admin: &Address, new_admin: &Address) {
+        env.events().publish((symbol_short!("set_admin"), admin.clone()), new_admin.clone());
+    }
+
+    /// Topics: `("delegate", delegator)`
+    /// Data:   `(previous_delegate, new_delegate)`
+    pub fn delegate(
+        env: &Env,
+        delegator: &Address,
+        previous_delegate: &Option<Address>,
+        new_delegate: &Address,
+    ) {
+        env.events().publish(
+            (symbol_short!("delegate"), delegator.clone()),
+            (previous_delegate.clone(), new_delegate.clone()),
+        );
+    }
+
+    /// Topics: `("votes", delegate)`
+    /// Data:   `(previous_votes, new_votes)`
+    pub fn votes(env: &Env, delegate: &Address, previous_votes: i128, new_votes: i128) {
+        env.events()
+            .publish((symbol_short!("votes"), delegate.clone()), (previous_votes, new_votes));
+    }
+}
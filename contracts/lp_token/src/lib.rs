@@ -1,16 +1,33 @@
 //! SEP-41 Liquidity Pool Token implementation for CoralSwap.
 //!
 //! This contract provides a standard-compliant LP token that can be
-//! minted/burned by the authorized CoralSwap Pair contract.
+//! minted/burned by the authorized CoralSwap Pair contract. It implements the
+//! SDK's `TokenInterface`, so it can be driven through `token::TokenClient`
+//! like any other Stellar token.
 
 #![no_std]
 
+#[cfg(test)]
+extern crate std;
+
+mod checkpoints;
 mod errors;
+mod events;
+mod permit;
 mod storage;
+mod votes;
 
 use errors::LpTokenError;
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
-use storage::{AllowanceEntry, LpTokenKey, TokenMetadata};
+use events::LpTokenEvents;
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token::TokenInterface, Address, BytesN, Env, String,
+};
+use storage::{
+    extend_instance_ttl, read_balance, read_delegate, read_nonce, write_balance, write_nonce,
+    AllowanceEntry, LpTokenKey, TokenMetadata,
+};
+
+pub use storage::{DelegationMessage, PermitMessage};
 
 #[contract]
 pub struct LpToken;
@@ -41,11 +58,200 @@ impl LpToken {
         // Initialize total supply to 0
         env.storage().instance().set(&LpTokenKey::TotalSupply, &0i128);
 
+        extend_instance_ttl(&env);
+
         Ok(())
     }
 
+    /// Mint new tokens to an address
+    /// Only callable by admin (pair contract)
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), LpTokenError> {
+        check_nonnegative_amount(amount)?;
+
+        // Get admin and require authorization
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+
+        // Increase balance
+        let current_balance = read_balance(&env, &to);
+        let new_balance = current_balance.checked_add(amount).ok_or(LpTokenError::Overflow)?;
+        write_balance(&env, &to, new_balance);
+        checkpoints::record_balance(&env, &to, new_balance);
+        votes::move_balance_votes(&env, None, Some(&to), amount)?;
+
+        // Increase total supply
+        let total_supply: i128 =
+            env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0);
+        let new_total_supply = total_supply.checked_add(amount).ok_or(LpTokenError::Overflow)?;
+        env.storage().instance().set(&LpTokenKey::TotalSupply, &new_total_supply);
+        checkpoints::record_total_supply(&env, new_total_supply);
+        extend_instance_ttl(&env);
+
+        LpTokenEvents::mint(&env, &admin, &to, amount);
+
+        Ok(())
+    }
+
+    /// Hand minting rights to `new_admin`
+    /// Requires authorization from the current admin
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), LpTokenError> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+
+        env.storage().instance().set(&LpTokenKey::Admin, &new_admin);
+        extend_instance_ttl(&env);
+
+        LpTokenEvents::set_admin(&env, &admin, &new_admin);
+
+        Ok(())
+    }
+
+    /// Get the admin allowed to mint
+    pub fn admin(env: Env) -> Result<Address, LpTokenError> {
+        Self::read_admin(&env)
+    }
+
+    /// Get the total supply
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0)
+    }
+
+    /// Get the balance of `id` at the end of `ledger`
+    /// `ledger` must have closed and be within the last `CHECKPOINT_WINDOW` ledgers
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> Result<i128, LpTokenError> {
+        checkpoints::balance_at(&env, &id, ledger)
+    }
+
+    /// Get the total supply at the end of `ledger`
+    /// `ledger` must have closed and be within the last `CHECKPOINT_WINDOW` ledgers
+    pub fn total_supply_at(env: Env, ledger: u32) -> Result<i128, LpTokenError> {
+        checkpoints::total_supply_at(&env, ledger)
+    }
+
+    /// Extend the TTL of the token's instance storage
+    /// Permissionless, so keepers can keep idle tokens alive
+    pub fn bump(env: Env) -> Result<(), LpTokenError> {
+        Self::read_admin(&env)?;
+        extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Approve `spender` on behalf of `owner` using an off-chain signature
+    /// Anyone may submit the permit; `owner` does not need to authorize the call
+    ///
+    /// `signature` must be `owner`'s ed25519 signature over the XDR encoding of
+    /// a `PermitMessage` carrying these arguments, `domain_separator()` and
+    /// `nonce(owner)`. `public_key` must be the key behind the `owner` account.
+    /// An invalid signature fails the host's ed25519 verification and traps.
+    ///
+    /// Only the master key of a `G...` account is accepted; the account's other
+    /// signers and thresholds are ignored, and contract addresses can't sign.
+    /// A permit can be submitted once, by anyone: callers that may race a
+    /// front-runner should check `allowance` first.
+    pub fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), LpTokenError> {
+        let nonce = read_nonce(&env, &owner);
+        let message = PermitMessage {
+            domain_separator: permit::domain_separator(&env),
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger,
+            nonce,
+        };
+        permit::verify(&env, &owner, &public_key, &message, &signature)?;
+
+        write_nonce(&env, &owner, nonce + 1);
+        Self::write_allowance(&env, &owner, &spender, amount, expiration_ledger)?;
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Delegate `delegator`'s voting power to `delegatee`
+    /// Requires authorization from `delegator`; delegate to yourself to vote directly
+    pub fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), LpTokenError> {
+        delegator.require_auth();
+
+        votes::delegate(&env, &delegator, &delegatee)?;
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Delegate on behalf of `delegator` using an off-chain signature
+    /// Anyone may submit the delegation; `delegator` does not need to authorize the call
+    ///
+    /// `signature` must be `delegator`'s ed25519 signature over the XDR encoding
+    /// of a `DelegationMessage` carrying these arguments, `domain_separator()`
+    /// and `nonce(delegator)`, and must be submitted by `expiration_ledger`.
+    pub fn delegate_by_sig(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        expiration_ledger: u32,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), LpTokenError> {
+        if expiration_ledger < env.ledger().sequence() {
+            return Err(LpTokenError::InvalidExpiration);
+        }
+
+        let nonce = read_nonce(&env, &delegator);
+        let message = DelegationMessage {
+            domain_separator: permit::domain_separator(&env),
+            delegator: delegator.clone(),
+            delegatee: delegatee.clone(),
+            expiration_ledger,
+            nonce,
+        };
+        permit::verify(&env, &delegator, &public_key, &message, &signature)?;
+
+        write_nonce(&env, &delegator, nonce + 1);
+        votes::delegate(&env, &delegator, &delegatee)?;
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the address `account` delegates its voting power to, if any
+    pub fn delegates(env: Env, account: Address) -> Option<Address> {
+        read_delegate(&env, &account)
+    }
+
+    /// Get the voting power delegated to `account` at the end of `ledger`
+    /// `ledger` must have closed and be within the last `CHECKPOINT_WINDOW` ledgers
+    pub fn get_votes(env: Env, account: Address, ledger: u32) -> Result<i128, LpTokenError> {
+        checkpoints::votes_at(&env, &account, ledger)
+    }
+
+    /// Get the voting power currently delegated to `account`
+    pub fn current_votes(env: Env, account: Address) -> i128 {
+        checkpoints::current_votes(&env, &account)
+    }
+
+    /// Get the next permit and signed-delegation nonce for `owner`
+    pub fn nonce(env: Env, owner: Address) -> u64 {
+        read_nonce(&env, &owner)
+    }
+
+    /// Get the domain separator permits for this token must commit to
+    pub fn domain_separator(env: Env) -> BytesN<32> {
+        permit::domain_separator(&env)
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for LpToken {
     /// Get the allowance for spender to transfer from `from`
-    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
         let key = LpTokenKey::Allowance(from, spender);
 
         if let Some(allowance_entry) =
@@ -63,175 +269,126 @@ impl LpToken {
 
     /// Set allowance for spender to transfer from `from`
     /// Requires authorization from `from`
-    pub fn approve(
-        env: Env,
-        from: Address,
-        spender: Address,
-        amount: i128,
-        expiration_ledger: u32,
-    ) -> Result<(), LpTokenError> {
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
         // Require authorization from the `from` address
         from.require_auth();
 
-        // Validate expiration ledger (unless setting to 0)
-        if amount != 0 && expiration_ledger < env.ledger().sequence() {
-            return Err(LpTokenError::Unauthorized);
-        }
-
-        let key = LpTokenKey::Allowance(from.clone(), spender.clone());
-
-        if amount == 0 {
-            // Remove allowance if amount is 0
-            env.storage().persistent().remove(&key);
-        } else {
-            let allowance_entry = AllowanceEntry { amount, expiration_ledger };
-            env.storage().persistent().set(&key, &allowance_entry);
-
-            // Set TTL for the allowance entry
-            let ledgers_to_live = expiration_ledger.saturating_sub(env.ledger().sequence());
-            env.storage().persistent().extend_ttl(&key, ledgers_to_live, ledgers_to_live);
-        }
-
-        // Emit approve event
-        env.events().publish(
-            (soroban_sdk::symbol_short!("approve"), from, spender),
-            (amount, expiration_ledger),
+        unwrap_or_panic(
+            &env,
+            Self::write_allowance(&env, &from, &spender, amount, expiration_ledger),
         );
-
-        Ok(())
     }
 
     /// Get the balance of an address
-    pub fn balance(env: Env, id: Address) -> i128 {
-        let key = LpTokenKey::Balance(id);
-        env.storage().persistent().get(&key).unwrap_or(0)
+    fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
     }
 
     /// Transfer tokens from `from` to `to`
     /// Requires authorization from `from`
-    pub fn transfer(
-        env: Env,
-        from: Address,
-        to: Address,
-        amount: i128,
-    ) -> Result<(), LpTokenError> {
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
         // Require authorization from the `from` address
         from.require_auth();
 
-        // Perform the transfer
-        Self::transfer_internal(&env, &from, &to, amount)?;
-
-        Ok(())
+        unwrap_or_panic(&env, Self::transfer_internal(&env, &from, &to, amount));
     }
 
     /// Transfer tokens from `from` to `to` using spender's allowance
     /// Requires authorization from `spender`
-    pub fn transfer_from(
-        env: Env,
-        spender: Address,
-        from: Address,
-        to: Address,
-        amount: i128,
-    ) -> Result<(), LpTokenError> {
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
         // Require authorization from the spender
         spender.require_auth();
 
-        // Check and deduct allowance
-        Self::spend_allowance(&env, &from, &spender, amount)?;
-
-        // Perform the transfer
-        Self::transfer_internal(&env, &from, &to, amount)?;
-
-        Ok(())
-    }
-
-    /// Mint new tokens to an address
-    /// Only callable by admin (pair contract)
-    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), LpTokenError> {
-        // Get admin and require authorization
-        let admin: Address =
-            env.storage().instance().get(&LpTokenKey::Admin).ok_or(LpTokenError::NotInitialized)?;
-
-        admin.require_auth();
-
-        // Increase balance
-        let balance_key = LpTokenKey::Balance(to.clone());
-        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
-        let new_balance = current_balance.checked_add(amount).ok_or(LpTokenError::Overflow)?;
-        env.storage().persistent().set(&balance_key, &new_balance);
-
-        // Increase total supply
-        let total_supply: i128 =
-            env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0);
-        let new_total_supply = total_supply.checked_add(amount).ok_or(LpTokenError::Overflow)?;
-        env.storage().instance().set(&LpTokenKey::TotalSupply, &new_total_supply);
-
-        // Emit mint event
-        env.events().publish((soroban_sdk::symbol_short!("mint"), admin, to), amount);
-
-        Ok(())
+        unwrap_or_panic(&env, check_nonnegative_amount(amount));
+        unwrap_or_panic(&env, Self::spend_allowance(&env, &from, &spender, amount));
+        unwrap_or_panic(&env, Self::transfer_internal(&env, &from, &to, amount));
     }
 
     /// Burn tokens from an address
     /// Requires authorization from `from`
-    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), LpTokenError> {
+    fn burn(env: Env, from: Address, amount: i128) {
         // Require authorization from the `from` address
         from.require_auth();
 
-        // Decrease balance
-        let balance_key = LpTokenKey::Balance(from.clone());
-        let current_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
-
-        if current_balance < amount {
-            return Err(LpTokenError::InsufficientBalance);
-        }
-
-        let new_balance = current_balance - amount;
-        if new_balance == 0 {
-            env.storage().persistent().remove(&balance_key);
-        } else {
-            env.storage().persistent().set(&balance_key, &new_balance);
-        }
-
-        // Decrease total supply
-        let total_supply: i128 =
-            env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0);
-        let new_total_supply = total_supply - amount;
-        env.storage().instance().set(&LpTokenKey::TotalSupply, &new_total_supply);
+        unwrap_or_panic(&env, Self::burn_internal(&env, &from, amount));
+    }
 
-        // Emit burn event
-        env.events().publish((soroban_sdk::symbol_short!("burn"), from), amount);
+    /// Burn tokens from `from` using spender's allowance
+    /// Requires authorization from `spender`
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        // Require authorization from the spender
+        spender.require_auth();
 
-        Ok(())
+        unwrap_or_panic(&env, check_nonnegative_amount(amount));
+        unwrap_or_panic(&env, Self::spend_allowance(&env, &from, &spender, amount));
+        unwrap_or_panic(&env, Self::burn_internal(&env, &from, amount));
     }
 
     /// Get the number of decimals
-    pub fn decimals(env: Env) -> u32 {
-        let metadata: TokenMetadata =
-            env.storage().instance().get(&LpTokenKey::Metadata).expect("Token not initialized");
-        metadata.decimals
+    fn decimals(env: Env) -> u32 {
+        Self::read_metadata(&env).decimals
     }
 
     /// Get the token name
-    pub fn name(env: Env) -> String {
-        let metadata: TokenMetadata =
-            env.storage().instance().get(&LpTokenKey::Metadata).expect("Token not initialized");
-        metadata.name
+    fn name(env: Env) -> String {
+        Self::read_metadata(&env).name
     }
 
     /// Get the token symbol
-    pub fn symbol(env: Env) -> String {
-        let metadata: TokenMetadata =
-            env.storage().instance().get(&LpTokenKey::Metadata).expect("Token not initialized");
-        metadata.symbol
+    fn symbol(env: Env) -> String {
+        Self::read_metadata(&env).symbol
     }
+}
 
-    /// Get the total supply
-    pub fn total_supply(env: Env) -> i128 {
-        env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0)
+// Internal helper functions
+
+impl LpToken {
+    fn read_admin(env: &Env) -> Result<Address, LpTokenError> {
+        env.storage().instance().get(&LpTokenKey::Admin).ok_or(LpTokenError::NotInitialized)
     }
 
-    // Internal helper functions
+    /// Reads the token metadata, failing with `NotInitialized` rather than
+    /// an opaque host panic before `initialize`.
+    fn read_metadata(env: &Env) -> TokenMetadata {
+        match env.storage().instance().get(&LpTokenKey::Metadata) {
+            Some(metadata) => metadata,
+            None => panic_with_error!(env, LpTokenError::NotInitialized),
+        }
+    }
+
+    /// Internal function to set an allowance, shared by `approve` and `permit`
+    fn write_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), LpTokenError> {
+        check_nonnegative_amount(amount)?;
+
+        // Validate expiration ledger (unless setting to 0)
+        if amount != 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(LpTokenError::InvalidExpiration);
+        }
+
+        let key = LpTokenKey::Allowance(from.clone(), spender.clone());
+
+        if amount == 0 {
+            // Remove allowance if amount is 0
+            env.storage().persistent().remove(&key);
+        } else {
+            let allowance_entry = AllowanceEntry { amount, expiration_ledger };
+            env.storage().persistent().set(&key, &allowance_entry);
+
+            // Set TTL for the allowance entry
+            let ledgers_to_live = expiration_ledger.saturating_sub(env.ledger().sequence());
+            env.storage().persistent().extend_ttl(&key, ledgers_to_live, ledgers_to_live);
+        }
+
+        LpTokenEvents::approve(env, from, spender, amount, expiration_ledger);
+
+        Ok(())
+    }
 
     /// Internal transfer function
     fn transfer_internal(
@@ -240,38 +397,59 @@ impl LpToken {
         to: &Address,
         amount: i128,
     ) -> Result<(), LpTokenError> {
-        if amount < 0 {
-            return Err(LpTokenError::InsufficientBalance);
-        }
+        check_nonnegative_amount(amount)?;
 
         if amount == 0 {
             return Ok(());
         }
 
         // Debit from sender
-        let from_key = LpTokenKey::Balance(from.clone());
-        let from_balance: i128 = env.storage().persistent().get(&from_key).unwrap_or(0);
+        let from_balance = read_balance(env, from);
 
         if from_balance < amount {
             return Err(LpTokenError::InsufficientBalance);
         }
 
-        let new_from_balance = from_balance - amount;
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&from_key);
-        } else {
-            env.storage().persistent().set(&from_key, &new_from_balance);
-        }
+        write_balance(env, from, from_balance - amount);
+        checkpoints::record_balance(env, from, from_balance - amount);
 
         // Credit to receiver
-        let to_key = LpTokenKey::Balance(to.clone());
-        let to_balance: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+        let to_balance = read_balance(env, to);
         let new_to_balance = to_balance.checked_add(amount).ok_or(LpTokenError::Overflow)?;
-        env.storage().persistent().set(&to_key, &new_to_balance);
+        write_balance(env, to, new_to_balance);
+        checkpoints::record_balance(env, to, new_to_balance);
+        votes::move_balance_votes(env, Some(from), Some(to), amount)?;
+        extend_instance_ttl(env);
 
-        // Emit transfer event
-        env.events()
-            .publish((soroban_sdk::symbol_short!("transfer"), from.clone(), to.clone()), amount);
+        LpTokenEvents::transfer(env, from, to, amount);
+
+        Ok(())
+    }
+
+    /// Internal burn function
+    fn burn_internal(env: &Env, from: &Address, amount: i128) -> Result<(), LpTokenError> {
+        check_nonnegative_amount(amount)?;
+
+        // Decrease balance
+        let current_balance = read_balance(env, from);
+
+        if current_balance < amount {
+            return Err(LpTokenError::InsufficientBalance);
+        }
+
+        write_balance(env, from, current_balance - amount);
+        checkpoints::record_balance(env, from, current_balance - amount);
+        votes::move_balance_votes(env, Some(from), None, amount)?;
+
+        // Decrease total supply
+        let total_supply: i128 =
+            env.storage().instance().get(&LpTokenKey::TotalSupply).unwrap_or(0);
+        let new_total_supply = total_supply - amount;
+        env.storage().instance().set(&LpTokenKey::TotalSupply, &new_total_supply);
+        checkpoints::record_total_supply(env, new_total_supply);
+        extend_instance_ttl(env);
+
+        LpTokenEvents::burn(env, from, amount);
 
         Ok(())
     }
@@ -283,6 +461,10 @@ impl LpToken {
         spender: &Address,
         amount: i128,
     ) -> Result<(), LpTokenError> {
+        if amount == 0 {
+            return Ok(());
+        }
+
         let key = LpTokenKey::Allowance(from.clone(), spender.clone());
 
         let allowance_entry: AllowanceEntry =
@@ -314,5 +496,20 @@ impl LpToken {
     }
 }
 
+fn check_nonnegative_amount(amount: i128) -> Result<(), LpTokenError> {
+    if amount < 0 {
+        return Err(LpTokenError::NegativeAmount);
+    }
+    Ok(())
+}
+
+/// `TokenInterface` methods cannot return `Result`, so errors surface as
+/// contract errors via `panic_with_error!`.
+fn unwrap_or_panic(env: &Env, result: Result<(), LpTokenError>) {
+    if let Err(error) = result {
+        panic_with_error!(env, error);
+    }
+}
+
 #[cfg(test)]
 mod test;
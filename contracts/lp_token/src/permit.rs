@@ -0,0 +1,107 @@
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Val};
+
+use crate::errors::LpTokenError;
+
+// Signed-permit approvals.
+//
+// A permit lets an LP holder authorize an allowance off-chain: they sign a
+// `PermitMessage` with their account's ed25519 key and anyone (typically the
+// router, within the same transaction that spends the allowance) submits it.
+// Signed delegations (`DelegationMessage`) work the same way.
+//
+// The signed bytes are the message's XDR encoding. The message commits to a
+// domain separator binding it to this token contract on this network, and to
+// the owner's current nonce, so a permit can't be replayed on another token,
+// another network, or a second time on this one. Permits and delegations
+// share the owner's nonce, and their XDR differs by field names, so one can't
+// be passed off as the other.
+//
+// Limits:
+// - Only the account's master ed25519 key can sign. Its signers, thresholds
+//   and master weight are never consulted, so a multisig account's master key
+//   alone suffices, and a master key the account has disabled still works.
+// - Only key-backed accounts (`G...`) can use permits. Contract addresses have
+//   no key to sign with and must `approve` directly.
+// - A permit is public once broadcast, and anyone may submit it first. That
+//   grants exactly the signed allowance but consumes the nonce, so a second
+//   submission of the same permit fails. Integrators should check for the
+//   allowance before submitting, as the router does.
+
+/// Strkey version byte for ed25519 account public keys (`G...`).
+const ACCOUNT_ID_VERSION_BYTE: u8 = 6 << 3;
+/// Length of an encoded account strkey: 35 bytes in base32.
+const ACCOUNT_STRKEY_LEN: usize = 56;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Returns the separator every permit for this token must commit to:
+/// `sha256(xdr(("permit", network_id, token_address)))`.
+pub fn domain_separator(env: &Env) -> BytesN<32> {
+    let domain =
+        (symbol_short!("permit"), env.ledger().network_id(), env.current_contract_address());
+    env.crypto().sha256(&domain.to_xdr(env)).into()
+}
+
+/// Returns the bytes the owner signs for `message`.
+pub fn signing_payload<M: Clone + IntoVal<Env, Val>>(env: &Env, message: &M) -> Bytes {
+    message.clone().to_xdr(env)
+}
+
+/// Checks that `signature` is `signer`'s signature over `message`.
+///
+/// Fails with `InvalidPermitSigner` if `public_key` does not control `signer`;
+/// a bad signature fails the host's ed25519 verification and traps.
+pub fn verify<M: Clone + IntoVal<Env, Val>>(
+    env: &Env,
+    signer: &Address,
+    public_key: &BytesN<32>,
+    message: &M,
+    signature: &BytesN<64>,
+) -> Result<(), LpTokenError> {
+    if account_address(env, public_key) != *signer {
+        return Err(LpTokenError::InvalidPermitSigner);
+    }
+    env.crypto().ed25519_verify(public_key, &signing_payload(env, message), signature);
+    Ok(())
+}
+
+/// Returns the Stellar account address (`G...`) controlled by `public_key`.
+pub fn account_address(env: &Env, public_key: &BytesN<32>) -> Address {
+    let mut payload = [0u8; 35];
+    payload[0] = ACCOUNT_ID_VERSION_BYTE;
+    payload[1..33].copy_from_slice(&public_key.to_array());
+    let checksum = crc16_xmodem(&payload[..33]);
+    payload[33..].copy_from_slice(&checksum.to_le_bytes());
+
+    let strkey = base32_encode(&payload);
+    Address::from_string_bytes(&Bytes::from_slice(env, &strkey))
+}
+
+/// CRC16-XModem, the checksum strkeys append to their payload.
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// RFC 4648 base32 without padding. 35 bytes encode to exactly 56 characters.
+fn base32_encode(data: &[u8; 35]) -> [u8; ACCOUNT_STRKEY_LEN] {
+    let mut out = [0u8; ACCOUNT_STRKEY_LEN];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    let mut index = 0;
+    for byte in data {
+        buffer = ((buffer << 8) | *byte as u32) & 0xfff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out[index] = BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize];
+            index += 1;
+        }
+    }
+    out
+}
@@ -1,4 +1,10 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String};
+
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+pub const BALANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const BALANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+pub const CHECKPOINT_WINDOW: u32 = BALANCE_BUMP_AMOUNT / 2; // ~15 days in 5s ledgers
 
 #[contracttype]
 pub struct TokenMetadata {
@@ -13,6 +19,47 @@ pub struct AllowanceEntry {
     pub expiration_ledger: u32,
 }
 
+/// An allowance signed off-chain by `owner` and submitted through `permit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitMessage {
+    pub domain_separator: BytesN<32>,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+}
+
+/// A delegation signed off-chain by `delegator` and submitted through
+/// `delegate_by_sig`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationMessage {
+    pub domain_separator: BytesN<32>,
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+}
+
+/// A balance, total supply or voting power as of the end of `ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub amount: i128,
+}
+
+/// Where a checkpoint history stands: `count` checkpoints have been written,
+/// and those before `first` are older than any ledger that can still be looked up.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CheckpointHead {
+    pub count: u32,
+    pub first: u32,
+}
+
 #[contracttype]
 pub enum LpTokenKey {
     Balance(Address),
@@ -20,4 +67,139 @@ pub enum LpTokenKey {
     Admin,
     TotalSupply,
     Metadata,
+    Nonce(Address),
+    /// An account's `n`th balance checkpoint, oldest first.
+    BalanceCheckpoint(Address, u32),
+    BalanceCheckpointHead(Address),
+    /// The `n`th total supply checkpoint, oldest first.
+    SupplyCheckpoint(u32),
+    SupplyCheckpointHead,
+    Delegate(Address),
+    /// A delegate's `n`th voting power checkpoint, oldest first.
+    VoteCheckpoint(Address, u32),
+    VoteCheckpointHead(Address),
+}
+
+/// Reads `id`'s balance, extending the entry's TTL if it exists.
+pub fn read_balance(env: &Env, id: &Address) -> i128 {
+    let key = LpTokenKey::Balance(id.clone());
+    match env.storage().persistent().get::<LpTokenKey, i128>(&key) {
+        Some(balance) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                BALANCE_LIFETIME_THRESHOLD,
+                BALANCE_BUMP_AMOUNT,
+            );
+            balance
+        }
+        None => 0,
+    }
+}
+
+/// Writes `id`'s balance and extends its TTL. Zero balances are removed
+/// rather than stored.
+pub fn write_balance(env: &Env, id: &Address, balance: i128) {
+    let key = LpTokenKey::Balance(id.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &balance);
+        env.storage().persistent().extend_ttl(
+            &key,
+            BALANCE_LIFETIME_THRESHOLD,
+            BALANCE_BUMP_AMOUNT,
+        );
+    }
+}
+
+/// Reads `owner`'s permit nonce, extending the entry's TTL if it exists.
+pub fn read_nonce(env: &Env, owner: &Address) -> u64 {
+    let key = LpTokenKey::Nonce(owner.clone());
+    match env.storage().persistent().get::<LpTokenKey, u64>(&key) {
+        Some(nonce) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                BALANCE_LIFETIME_THRESHOLD,
+                BALANCE_BUMP_AMOUNT,
+            );
+            nonce
+        }
+        None => 0,
+    }
+}
+
+/// Writes `owner`'s permit nonce and extends its TTL.
+pub fn write_nonce(env: &Env, owner: &Address, nonce: u64) {
+    let key = LpTokenKey::Nonce(owner.clone());
+    env.storage().persistent().set(&key, &nonce);
+    env.storage().persistent().extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Reads the address `delegator` delegates its votes to, extending the
+/// entry's TTL if it exists.
+pub fn read_delegate(env: &Env, delegator: &Address) -> Option<Address> {
+    let key = LpTokenKey::Delegate(delegator.clone());
+    let delegate = env.storage().persistent().get(&key);
+    if delegate.is_some() {
+        env.storage().persistent().extend_ttl(
+            &key,
+            BALANCE_LIFETIME_THRESHOLD,
+            BALANCE_BUMP_AMOUNT,
+        );
+    }
+    delegate
+}
+
+/// Writes `delegator`'s delegate and extends its TTL.
+pub fn write_delegate(env: &Env, delegator: &Address, delegatee: &Address) {
+    let key = LpTokenKey::Delegate(delegator.clone());
+    env.storage().persistent().set(&key, delegatee);
+    env.storage().persistent().extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Reads the checkpoint under `key`, extending its TTL if it exists.
+pub fn read_checkpoint(env: &Env, key: &LpTokenKey) -> Option<Checkpoint> {
+    let checkpoint = env.storage().persistent().get::<LpTokenKey, Checkpoint>(key)?;
+    env.storage().persistent().extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    Some(checkpoint)
+}
+
+/// Writes the checkpoint under `key` and extends its TTL.
+pub fn write_checkpoint(env: &Env, key: &LpTokenKey, checkpoint: &Checkpoint) {
+    env.storage().persistent().set(key, checkpoint);
+    env.storage().persistent().extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Extends the TTL of the checkpoint under `key` to the full bump amount,
+/// however much it has left. Used on the checkpoint a new one supersedes, so
+/// it outlives every lookup that may still land on it.
+pub fn retain_checkpoint(env: &Env, key: &LpTokenKey) {
+    env.storage().persistent().extend_ttl(key, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_AMOUNT);
+}
+
+/// Reads the checkpoint head under `key`, extending its TTL if it exists.
+pub fn read_checkpoint_head(env: &Env, key: &LpTokenKey) -> CheckpointHead {
+    match env.storage().persistent().get::<LpTokenKey, CheckpointHead>(key) {
+        Some(head) => {
+            env.storage().persistent().extend_ttl(
+                key,
+                BALANCE_LIFETIME_THRESHOLD,
+                BALANCE_BUMP_AMOUNT,
+            );
+            head
+        }
+        None => CheckpointHead::default(),
+    }
+}
+
+/// Writes the checkpoint head under `key` and extends its TTL.
+pub fn write_checkpoint_head(env: &Env, key: &LpTokenKey, head: &CheckpointHead) {
+    env.storage().persistent().set(key, head);
+    env.storage().persistent().extend_ttl(key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Extend instance storage TTL to keep the token alive. Called on every
+/// state-changing entrypoint.
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
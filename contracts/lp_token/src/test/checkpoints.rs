@@ -0,0 +1,176 @@
+use super::setup;
+use crate::{
+    errors::LpTokenError,
+    storage::{CheckpointHead, LpTokenKey, BALANCE_BUMP_AMOUNT, CHECKPOINT_WINDOW},
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn set_ledger(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = sequence);
+}
+
+#[test]
+fn test_balance_at_tracks_mint_transfer_and_burn() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    set_ledger(&env, 20);
+    client.transfer(&alice, &bob, &400);
+    set_ledger(&env, 30);
+    client.burn(&bob, &100);
+    set_ledger(&env, 40);
+
+    assert_eq!(client.balance_at(&alice, &9), 0);
+    assert_eq!(client.balance_at(&alice, &10), 1_000);
+    assert_eq!(client.balance_at(&alice, &19), 1_000);
+    assert_eq!(client.balance_at(&alice, &20), 600);
+    assert_eq!(client.balance_at(&bob, &20), 400);
+    assert_eq!(client.balance_at(&bob, &29), 400);
+    assert_eq!(client.balance_at(&bob, &30), 300);
+    assert_eq!(client.balance_at(&bob, &39), 300);
+}
+
+#[test]
+fn test_total_supply_at_tracks_mint_and_burn() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    set_ledger(&env, 20);
+    client.burn(&alice, &250);
+    set_ledger(&env, 30);
+
+    assert_eq!(client.total_supply_at(&9), 0);
+    assert_eq!(client.total_supply_at(&15), 1_000);
+    assert_eq!(client.total_supply_at(&20), 750);
+    assert_eq!(client.total_supply_at(&29), 750);
+}
+
+#[test]
+fn test_changes_within_a_ledger_share_one_checkpoint() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    client.transfer(&alice, &bob, &300);
+    client.transfer(&alice, &bob, &200);
+    set_ledger(&env, 11);
+
+    assert_eq!(client.balance_at(&alice, &10), 500);
+    env.as_contract(&client.address, || {
+        let head_key = LpTokenKey::BalanceCheckpointHead(alice.clone());
+        assert_eq!(crate::storage::read_checkpoint_head(&env, &head_key).count, 1);
+    });
+}
+
+#[test]
+fn test_lookups_reach_back_one_window() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    set_ledger(&env, 20);
+    client.mint(&alice, &500);
+    set_ledger(&env, 10 + CHECKPOINT_WINDOW);
+
+    assert_eq!(client.balance_at(&alice, &10), 1_000);
+    assert_eq!(client.balance_at(&alice, &20), 1_500);
+    assert_eq!(client.total_supply_at(&10), 1_000);
+    assert_eq!(client.try_balance_at(&alice, &9), Err(Ok(LpTokenError::LedgerTooOld)));
+    assert_eq!(client.try_total_supply_at(&9), Err(Ok(LpTokenError::LedgerTooOld)));
+}
+
+#[test]
+fn test_lookups_outlive_the_checkpoint_ttl() {
+    let (env, client, _) = setup();
+    env.budget().reset_unlimited();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    // A burst of changes, then steady ones over one and a half TTL bumps,
+    // often enough to keep the token itself alive. The early checkpoints lapse
+    // into the archive, and reading one would trap.
+    let ledgers: [u32; 88] = core::array::from_fn(|i| match i as u32 {
+        i @ 0..=39 => 100 + i,
+        i => 100 + (i - 39) * 17_000,
+    });
+    for (i, ledger) in ledgers.iter().enumerate() {
+        set_ledger(&env, *ledger);
+        client.mint(&alice, &100);
+        if i % 2 == 1 {
+            client.transfer(&alice, &bob, &50);
+        }
+    }
+    let now = ledgers[87] + 1;
+    assert!(now > ledgers[0] + BALANCE_BUMP_AMOUNT + CHECKPOINT_WINDOW);
+    set_ledger(&env, now);
+
+    let oldest = now - CHECKPOINT_WINDOW;
+    for (i, ledger) in ledgers.iter().enumerate() {
+        let minted = 100 * (i as i128 + 1);
+        let transferred = 50 * ((i as i128 + 1) / 2);
+        if *ledger >= oldest {
+            assert_eq!(client.balance_at(&alice, ledger), minted - transferred);
+            assert_eq!(client.balance_at(&bob, ledger), transferred);
+            assert_eq!(client.total_supply_at(ledger), minted);
+        } else {
+            assert_eq!(client.try_balance_at(&alice, ledger), Err(Ok(LpTokenError::LedgerTooOld)));
+        }
+    }
+
+    // The window starts between two changes, so its first lookup lands on a
+    // checkpoint from before it.
+    let first = ledgers.iter().rposition(|ledger| *ledger <= oldest).unwrap();
+    assert!(ledgers[first] < oldest);
+    let changes = first as i128 + 1;
+    assert_eq!(client.balance_at(&alice, &oldest), 100 * changes - 50 * (changes / 2));
+    assert_eq!(client.try_balance_at(&bob, &(oldest - 1)), Err(Ok(LpTokenError::LedgerTooOld)));
+    env.as_contract(&client.address, || {
+        let head_key = LpTokenKey::BalanceCheckpointHead(alice.clone());
+        assert_eq!(
+            crate::storage::read_checkpoint_head(&env, &head_key),
+            CheckpointHead { count: 88, first: first as u32 }
+        );
+    });
+}
+
+#[test]
+fn test_recording_writes_one_checkpoint_entry() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    set_ledger(&env, 11);
+    client.mint(&alice, &500);
+
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        assert!(persistent.has(&LpTokenKey::BalanceCheckpoint(alice.clone(), 0)));
+        assert_eq!(
+            persistent.get(&LpTokenKey::BalanceCheckpoint(alice.clone(), 1)),
+            Some(crate::storage::Checkpoint { ledger: 11, amount: 1_500 })
+        );
+        assert_eq!(
+            persistent.get(&LpTokenKey::BalanceCheckpointHead(alice.clone())),
+            Some(CheckpointHead { count: 2, first: 0 })
+        );
+    });
+}
+
+#[test]
+fn test_lookups_require_a_closed_ledger() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+
+    assert_eq!(client.try_balance_at(&alice, &10), Err(Ok(LpTokenError::LedgerNotPast)));
+    assert_eq!(client.try_total_supply_at(&11), Err(Ok(LpTokenError::LedgerNotPast)));
+}
@@ -0,0 +1,189 @@
+use super::{setup, Holder};
+use crate::{errors::LpTokenError, LpTokenClient, PermitMessage};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::TokenClient,
+    Address, BytesN, Env, IntoVal, String,
+};
+
+impl Holder {
+    fn sign(
+        &self,
+        env: &Env,
+        client: &LpTokenClient,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+    ) -> BytesN<64> {
+        let message = PermitMessage {
+            domain_separator: client.domain_separator(),
+            owner: self.address.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger,
+            nonce,
+        };
+        self.sign_message(env, message)
+    }
+}
+
+#[test]
+fn test_permit_sets_allowance_without_owner_auth() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 1);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 1_000, 0);
+
+    env.set_auths(&[]);
+    client.permit(&holder.address, &spender, &500, &1_000, &holder.public_key(&env), &signature);
+
+    assert_eq!(client.allowance(&holder.address, &spender), 500);
+    assert_eq!(client.nonce(&holder.address), 1);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.0, client.address);
+    assert_eq!(
+        event.1,
+        (symbol_short!("approve"), holder.address.clone(), spender.clone()).into_val(&env)
+    );
+}
+
+#[test]
+fn test_permit_allowance_is_spendable() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 2);
+    let (spender, recipient) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&holder.address, &1_000);
+
+    let signature = holder.sign(&env, &client, &spender, 400, 1_000, 0);
+    client.permit(&holder.address, &spender, &400, &1_000, &holder.public_key(&env), &signature);
+    TokenClient::new(&env, &client.address).transfer_from(
+        &spender,
+        &holder.address,
+        &recipient,
+        &400,
+    );
+
+    assert_eq!(client.balance(&recipient), 400);
+    assert_eq!(client.allowance(&holder.address, &spender), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_permit_cannot_be_replayed() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 3);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 1_000, 0);
+    let public_key = holder.public_key(&env);
+
+    client.permit(&holder.address, &spender, &500, &1_000, &public_key, &signature);
+    client.permit(&holder.address, &spender, &500, &1_000, &public_key, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_permit_rejects_tampered_amount() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 4);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 1_000, 0);
+
+    client.permit(&holder.address, &spender, &5_000, &1_000, &holder.public_key(&env), &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_permit_is_bound_to_its_token() {
+    let (env, client, admin) = setup();
+    let other = LpTokenClient::new(&env, &env.register_contract(None, crate::LpToken));
+    other.initialize(&admin, &7, &String::from_str(&env, "Other"), &String::from_str(&env, "OTH"));
+    let holder = Holder::new(&env, 5);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 1_000, 0);
+
+    other.permit(&holder.address, &spender, &500, &1_000, &holder.public_key(&env), &signature);
+}
+
+#[test]
+fn test_permit_rejects_key_of_another_account() {
+    let (env, client, _) = setup();
+    let (holder, impostor) = (Holder::new(&env, 6), Holder::new(&env, 7));
+    let spender = Address::generate(&env);
+    let signature = impostor.sign(&env, &client, &spender, 500, 1_000, 0);
+
+    assert_eq!(
+        client.try_permit(
+            &holder.address,
+            &spender,
+            &500,
+            &1_000,
+            &impostor.public_key(&env),
+            &signature
+        ),
+        Err(Ok(LpTokenError::InvalidPermitSigner))
+    );
+}
+
+#[test]
+fn test_permit_rejects_past_expiration() {
+    let (env, client, _) = setup();
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    let holder = Holder::new(&env, 8);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 99, 0);
+
+    assert_eq!(
+        client.try_permit(
+            &holder.address,
+            &spender,
+            &500,
+            &99,
+            &holder.public_key(&env),
+            &signature
+        ),
+        Err(Ok(LpTokenError::InvalidExpiration))
+    );
+    assert_eq!(client.nonce(&holder.address), 0);
+}
+
+#[test]
+fn test_permit_cannot_be_signed_for_a_contract() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 9);
+    let (owner, spender) = (Address::generate(&env), Address::generate(&env));
+    let message = PermitMessage {
+        domain_separator: client.domain_separator(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount: 500,
+        expiration_ledger: 1_000,
+        nonce: 0,
+    };
+    let signature = holder.sign_message(&env, message);
+
+    assert_eq!(
+        client.try_permit(&owner, &spender, &500, &1_000, &holder.public_key(&env), &signature),
+        Err(Ok(LpTokenError::InvalidPermitSigner))
+    );
+}
+
+#[test]
+fn test_front_run_permit_grants_only_the_signed_allowance() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 10);
+    let spender = Address::generate(&env);
+    let signature = holder.sign(&env, &client, &spender, 500, 1_000, 0);
+    let public_key = holder.public_key(&env);
+
+    // A third party submits the permit before the intended caller does.
+    env.set_auths(&[]);
+    client.permit(&holder.address, &spender, &500, &1_000, &public_key, &signature);
+
+    assert_eq!(client.allowance(&holder.address, &spender), 500);
+    assert!(client
+        .try_permit(&holder.address, &spender, &500, &1_000, &public_key, &signature)
+        .is_err());
+    assert_eq!(client.allowance(&holder.address, &spender), 500);
+}
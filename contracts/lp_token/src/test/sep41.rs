@@ -0,0 +1,239 @@
+use super::setup;
+use crate::{errors::LpTokenError, LpToken, LpTokenClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, Events, Ledger},
+    token::TokenClient,
+    Address, Env, IntoVal, String, Symbol,
+};
+
+/// Asserts that `result` failed with the contract error `expected`.
+fn assert_error<T: core::fmt::Debug, E: core::fmt::Debug>(
+    result: Result<T, Result<soroban_sdk::Error, E>>,
+    expected: LpTokenError,
+) {
+    match result {
+        Err(Ok(error)) => assert_eq!(error, expected.into()),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
+
+/// Returns the address that authorized the most recent top-level call.
+fn last_authorizer(env: &Env) -> Address {
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    auths[0].0.clone()
+}
+
+// ── Metadata ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_metadata() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.name(), String::from_str(&env, "CORAL-LP USDC/XLM"));
+    assert_eq!(token.symbol(), String::from_str(&env, "CLP-USDC-XLM"));
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let (env, client, admin) = setup();
+
+    let name = String::from_str(&env, "Other");
+    let result = client.try_initialize(&admin, &18, &name, &name);
+    assert_eq!(result, Err(Ok(LpTokenError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_metadata_before_initialize_fails_with_error() {
+    let env = Env::default();
+    let token = TokenClient::new(&env, &env.register_contract(None, LpToken));
+
+    assert_error(token.try_decimals(), LpTokenError::NotInitialized);
+    assert_error(token.try_name(), LpTokenError::NotInitialized);
+    assert_error(token.try_symbol(), LpTokenError::NotInitialized);
+    assert_eq!(token.balance(&Address::generate(&env)), 0);
+}
+
+// ── Mint and admin ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_mint_requires_admin() {
+    let (env, client, admin) = setup();
+    let user = Address::generate(&env);
+
+    client.mint(&user, &1_000);
+    assert_eq!(last_authorizer(&env), admin);
+    assert_eq!(client.balance(&user), 1_000);
+    assert_eq!(client.total_supply(), 1_000);
+
+    assert_eq!(client.try_mint(&user, &-1), Err(Ok(LpTokenError::NegativeAmount)));
+}
+
+#[test]
+fn test_set_admin_rotates_minter() {
+    let (env, client, admin) = setup();
+    let new_admin = Address::generate(&env);
+
+    client.set_admin(&new_admin);
+    assert_eq!(last_authorizer(&env), admin.clone());
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, (Symbol::new(&env, "set_admin"), admin).into_val(&env));
+    assert_eq!(client.admin(), new_admin);
+
+    let user = Address::generate(&env);
+    client.mint(&user, &5);
+    assert_eq!(last_authorizer(&env), new_admin);
+}
+
+// ── Transfer ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_transfer() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+
+    token.transfer(&alice, &bob, &400);
+    assert_eq!(last_authorizer(&env), alice.clone());
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.0, client.address);
+    assert_eq!(event.1, (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env));
+
+    assert_eq!(token.balance(&alice), 600);
+    assert_eq!(token.balance(&bob), 400);
+    assert_eq!(client.total_supply(), 1_000);
+}
+
+#[test]
+fn test_transfer_rejects_insufficient_balance_and_negative_amounts() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &100);
+
+    assert_error(token.try_transfer(&alice, &bob, &101), LpTokenError::InsufficientBalance);
+    assert_error(token.try_transfer(&alice, &bob, &-1), LpTokenError::NegativeAmount);
+    assert_eq!(token.balance(&alice), 100);
+}
+
+// ── Allowances ───────────────────────────────────────────────────────────────
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, bob, spender) =
+        (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+
+    token.approve(&alice, &spender, &500, &200);
+    assert_eq!(last_authorizer(&env), alice.clone());
+    assert_eq!(token.allowance(&alice, &spender), 500);
+
+    token.transfer_from(&spender, &alice, &bob, &300);
+    assert_eq!(last_authorizer(&env), spender.clone());
+    assert_eq!(token.allowance(&alice, &spender), 200);
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(token.balance(&bob), 300);
+
+    assert_error(
+        token.try_transfer_from(&spender, &alice, &bob, &201),
+        LpTokenError::InsufficientAllowance,
+    );
+}
+
+#[test]
+fn test_allowance_expires() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, bob, spender) =
+        (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+
+    token.approve(&alice, &spender, &500, &10);
+    env.ledger().with_mut(|li| li.sequence_number = 11);
+
+    assert_eq!(token.allowance(&alice, &spender), 0);
+    assert_error(
+        token.try_transfer_from(&spender, &alice, &bob, &1),
+        LpTokenError::InsufficientAllowance,
+    );
+}
+
+#[test]
+fn test_approve_rejects_negative_amounts_and_past_expiration() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, spender) = (Address::generate(&env), Address::generate(&env));
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+
+    assert_error(token.try_approve(&alice, &spender, &-1, &200), LpTokenError::NegativeAmount);
+    assert_error(token.try_approve(&alice, &spender, &1, &99), LpTokenError::InvalidExpiration);
+
+    // Clearing an allowance ignores the expiration ledger
+    token.approve(&alice, &spender, &0, &0);
+    assert_eq!(token.allowance(&alice, &spender), 0);
+}
+
+// ── Burn ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_burn() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let alice = Address::generate(&env);
+    client.mint(&alice, &1_000);
+
+    token.burn(&alice, &250);
+    assert_eq!(last_authorizer(&env), alice.clone());
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, (symbol_short!("burn"), alice.clone()).into_val(&env));
+    assert_eq!(token.balance(&alice), 750);
+    assert_eq!(client.total_supply(), 750);
+
+    assert_error(token.try_burn(&alice, &-1), LpTokenError::NegativeAmount);
+    assert_error(token.try_burn(&alice, &751), LpTokenError::InsufficientBalance);
+    assert_eq!(client.total_supply(), 750);
+}
+
+#[test]
+fn test_burn_from() {
+    let (env, client, _) = setup();
+    let token = TokenClient::new(&env, &client.address);
+    let (alice, spender) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+    token.approve(&alice, &spender, &400, &200);
+
+    token.burn_from(&spender, &alice, &300);
+    let auths = env.auths();
+    assert_eq!(auths[0].0, spender);
+    assert_eq!(
+        auths[0].1.function,
+        AuthorizedFunction::Contract((
+            client.address.clone(),
+            symbol_short!("burn_from"),
+            (spender.clone(), alice.clone(), 300_i128).into_val(&env),
+        ))
+    );
+    assert_eq!(token.balance(&alice), 700);
+    assert_eq!(token.allowance(&alice, &spender), 100);
+    assert_eq!(client.total_supply(), 700);
+
+    assert_error(token.try_burn_from(&spender, &alice, &101), LpTokenError::InsufficientAllowance);
+    assert_error(token.try_burn_from(&spender, &alice, &-1), LpTokenError::NegativeAmount);
+}
+
+#[test]
+fn test_driven_through_lp_token_client() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &10);
+
+    // The trait methods are also exposed on the contract's own client
+    LpTokenClient::new(&env, &client.address).transfer(&alice, &bob, &10);
+    assert_eq!(client.balance(&bob), 10);
+}
@@ -0,0 +1,78 @@
+use super::setup;
+use crate::storage::{LpTokenKey, BALANCE_BUMP_AMOUNT, INSTANCE_BUMP_AMOUNT};
+use soroban_sdk::{
+    testutils::{
+        storage::{Instance, Persistent},
+        Address as _, Ledger,
+    },
+    Address, Env,
+};
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_mint_extends_balance_and_instance_ttl() {
+    let (env, client, _) = setup();
+    let user = Address::generate(&env);
+
+    client.mint(&user, &1_000);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            env.storage().persistent().get_ttl(&LpTokenKey::Balance(user.clone())),
+            BALANCE_BUMP_AMOUNT
+        );
+        assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+    });
+}
+
+#[test]
+fn test_balance_read_extends_ttl() {
+    let (env, client, _) = setup();
+    let user = Address::generate(&env);
+    client.mint(&user, &1_000);
+
+    advance_ledgers(&env, BALANCE_BUMP_AMOUNT - 10);
+    assert_eq!(client.balance(&user), 1_000);
+
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            env.storage().persistent().get_ttl(&LpTokenKey::Balance(user.clone())),
+            BALANCE_BUMP_AMOUNT
+        );
+    });
+}
+
+#[test]
+fn test_transfer_extends_both_balances() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+
+    advance_ledgers(&env, BALANCE_BUMP_AMOUNT - 10);
+    client.transfer(&alice, &bob, &400);
+
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        assert_eq!(persistent.get_ttl(&LpTokenKey::Balance(alice.clone())), BALANCE_BUMP_AMOUNT);
+        assert_eq!(persistent.get_ttl(&LpTokenKey::Balance(bob.clone())), BALANCE_BUMP_AMOUNT);
+    });
+}
+
+#[test]
+fn test_bump_extends_instance_ttl() {
+    let (env, client, _) = setup();
+
+    advance_ledgers(&env, INSTANCE_BUMP_AMOUNT - 10);
+    env.as_contract(&client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), 10);
+    });
+
+    client.bump();
+
+    env.as_contract(&client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+    });
+}
@@ -0,0 +1,216 @@
+use super::{setup, Holder};
+use crate::{
+    errors::LpTokenError,
+    storage::{CheckpointHead, LpTokenKey, CHECKPOINT_WINDOW},
+    DelegationMessage, LpTokenClient,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, IntoVal,
+};
+
+fn set_ledger(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = sequence);
+}
+
+fn sign_delegation(
+    env: &Env,
+    client: &LpTokenClient,
+    holder: &Holder,
+    delegatee: &Address,
+    expiration_ledger: u32,
+) -> BytesN<64> {
+    holder.sign_message(
+        env,
+        DelegationMessage {
+            domain_separator: client.domain_separator(),
+            delegator: holder.address.clone(),
+            delegatee: delegatee.clone(),
+            expiration_ledger,
+            nonce: client.nonce(&holder.address),
+        },
+    )
+}
+
+#[test]
+fn test_balances_carry_no_votes_until_delegated() {
+    let (env, client, _) = setup();
+    let alice = Address::generate(&env);
+    client.mint(&alice, &1_000);
+
+    assert_eq!(client.current_votes(&alice), 0);
+    assert_eq!(client.delegates(&alice), None);
+
+    client.delegate(&alice, &alice);
+
+    assert_eq!(client.current_votes(&alice), 1_000);
+    assert_eq!(client.delegates(&alice), Some(alice.clone()));
+}
+
+#[test]
+fn test_delegate_emits_events() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+
+    client.delegate(&alice, &bob);
+
+    let events = env.events().all();
+    let delegate_event = events.get(events.len() - 2).unwrap();
+    assert_eq!(delegate_event.1, (symbol_short!("delegate"), alice.clone()).into_val(&env));
+    let votes_event = events.last().unwrap();
+    assert_eq!(votes_event.1, (symbol_short!("votes"), bob.clone()).into_val(&env));
+}
+
+#[test]
+fn test_transfers_move_votes_between_delegates() {
+    let (env, client, _) = setup();
+    let (alice, bob, carol) =
+        (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    client.mint(&alice, &1_000);
+    client.delegate(&alice, &carol);
+    client.delegate(&bob, &bob);
+
+    client.transfer(&alice, &bob, &400);
+    assert_eq!(client.current_votes(&carol), 600);
+    assert_eq!(client.current_votes(&bob), 400);
+
+    client.burn(&bob, &100);
+    assert_eq!(client.current_votes(&bob), 300);
+
+    // Undelegated recipients take votes out of circulation.
+    let dave = Address::generate(&env);
+    client.transfer(&alice, &dave, &600);
+    assert_eq!(client.current_votes(&carol), 0);
+}
+
+#[test]
+fn test_redelegation_moves_existing_votes() {
+    let (env, client, _) = setup();
+    let (vault, bob, carol) =
+        (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    client.mint(&vault, &1_000);
+
+    client.delegate(&vault, &bob);
+    client.delegate(&vault, &carol);
+
+    assert_eq!(client.current_votes(&bob), 0);
+    assert_eq!(client.current_votes(&carol), 1_000);
+    assert_eq!(client.delegates(&vault), Some(carol));
+}
+
+#[test]
+fn test_get_votes_reads_history() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    set_ledger(&env, 10);
+    client.mint(&alice, &1_000);
+    client.delegate(&alice, &bob);
+    set_ledger(&env, 20);
+    client.transfer(&alice, &Address::generate(&env), &250);
+    set_ledger(&env, 30);
+
+    assert_eq!(client.get_votes(&bob, &9), 0);
+    assert_eq!(client.get_votes(&bob, &10), 1_000);
+    assert_eq!(client.get_votes(&bob, &25), 750);
+    assert_eq!(client.try_get_votes(&bob, &30), Err(Ok(LpTokenError::LedgerNotPast)));
+}
+
+#[test]
+fn test_vote_lookups_are_bounded_by_the_window() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    set_ledger(&env, 1);
+    client.delegate(&alice, &bob);
+    client.mint(&alice, &100);
+    set_ledger(&env, 2);
+    client.mint(&alice, &100);
+    set_ledger(&env, 2 + CHECKPOINT_WINDOW);
+    client.mint(&alice, &100);
+    set_ledger(&env, 3 + CHECKPOINT_WINDOW);
+
+    assert_eq!(client.get_votes(&bob, &3), 200);
+    assert_eq!(client.get_votes(&bob, &(2 + CHECKPOINT_WINDOW)), 300);
+    assert_eq!(client.try_get_votes(&bob, &2), Err(Ok(LpTokenError::LedgerTooOld)));
+    assert_eq!(client.current_votes(&bob), 300);
+    env.as_contract(&client.address, || {
+        let head_key = LpTokenKey::VoteCheckpointHead(bob.clone());
+        assert_eq!(
+            env.storage().persistent().get(&head_key),
+            Some(CheckpointHead { count: 3, first: 1 })
+        );
+    });
+}
+
+#[test]
+fn test_delegate_requires_delegator_auth() {
+    let (env, client, _) = setup();
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    client.delegate(&alice, &bob);
+
+    assert_eq!(env.auths()[0].0, alice);
+}
+
+#[test]
+fn test_delegate_by_sig() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 11);
+    let delegatee = Address::generate(&env);
+    client.mint(&holder.address, &1_000);
+    let signature = sign_delegation(&env, &client, &holder, &delegatee, 100);
+
+    env.set_auths(&[]);
+    client.delegate_by_sig(&holder.address, &delegatee, &100, &holder.public_key(&env), &signature);
+
+    assert_eq!(client.current_votes(&delegatee), 1_000);
+    assert_eq!(client.nonce(&holder.address), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_delegate_by_sig_cannot_be_replayed() {
+    let (env, client, _) = setup();
+    let holder = Holder::new(&env, 12);
+    let delegatee = Address::generate(&env);
+    let signature = sign_delegation(&env, &client, &holder, &delegatee, 100);
+    let public_key = holder.public_key(&env);
+
+    client.delegate_by_sig(&holder.address, &delegatee, &100, &public_key, &signature);
+    client.delegate_by_sig(&holder.address, &delegatee, &100, &public_key, &signature);
+}
+
+#[test]
+fn test_delegate_by_sig_rejects_expired_and_foreign_keys() {
+    let (env, client, _) = setup();
+    let (holder, impostor) = (Holder::new(&env, 13), Holder::new(&env, 14));
+    let delegatee = Address::generate(&env);
+    set_ledger(&env, 50);
+
+    let signature = sign_delegation(&env, &client, &holder, &delegatee, 49);
+    assert_eq!(
+        client.try_delegate_by_sig(
+            &holder.address,
+            &delegatee,
+            &49,
+            &holder.public_key(&env),
+            &signature
+        ),
+        Err(Ok(LpTokenError::InvalidExpiration))
+    );
+
+    let signature = sign_delegation(&env, &client, &impostor, &delegatee, 100);
+    assert_eq!(
+        client.try_delegate_by_sig(
+            &holder.address,
+            &delegatee,
+            &100,
+            &impostor.public_key(&env),
+            &signature
+        ),
+        Err(Ok(LpTokenError::InvalidPermitSigner))
+    );
+}
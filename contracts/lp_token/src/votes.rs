@@ -0,0 +1,62 @@
+use soroban_sdk::{Address, Env};
+
+use crate::checkpoints::{current_votes, record_votes};
+use crate::errors::LpTokenError;
+use crate::events::LpTokenEvents;
+use crate::storage::{read_balance, read_delegate, write_delegate};
+
+// Vote delegation.
+//
+// A holder's balance counts towards the voting power of the address it
+// delegates to, and nowhere until it delegates (to itself, to vote directly).
+// Each delegate's voting power is checkpointed like balances, so governance
+// can read it as of a past ledger. Contracts holding LP on behalf of users,
+// such as vaults, delegate like any other holder.
+
+/// Points `delegator`'s voting power at `delegatee`, moving its current
+/// balance's votes away from any previous delegate.
+pub fn delegate(env: &Env, delegator: &Address, delegatee: &Address) -> Result<(), LpTokenError> {
+    let previous = read_delegate(env, delegator);
+    write_delegate(env, delegator, delegatee);
+    LpTokenEvents::delegate(env, delegator, &previous, delegatee);
+
+    let balance = read_balance(env, delegator);
+    move_votes(env, previous.as_ref(), Some(delegatee), balance)
+}
+
+/// Moves the votes carried by `amount` tokens changing hands from `from` to
+/// `to`. `None` is an undelegated holder, or the mint/burn side.
+pub fn move_balance_votes(
+    env: &Env,
+    from: Option<&Address>,
+    to: Option<&Address>,
+    amount: i128,
+) -> Result<(), LpTokenError> {
+    let from_delegate = from.and_then(|from| read_delegate(env, from));
+    let to_delegate = to.and_then(|to| read_delegate(env, to));
+    move_votes(env, from_delegate.as_ref(), to_delegate.as_ref(), amount)
+}
+
+fn move_votes(
+    env: &Env,
+    from: Option<&Address>,
+    to: Option<&Address>,
+    amount: i128,
+) -> Result<(), LpTokenError> {
+    if amount == 0 || from == to {
+        return Ok(());
+    }
+    if let Some(from) = from {
+        let previous = current_votes(env, from);
+        let votes = previous.checked_sub(amount).ok_or(LpTokenError::Overflow)?;
+        record_votes(env, from, votes);
+        LpTokenEvents::votes(env, from, previous, votes);
+    }
+    if let Some(to) = to {
+        let previous = current_votes(env, to);
+        let votes = previous.checked_add(amount).ok_or(LpTokenError::Overflow)?;
+        record_votes(env, to, votes);
+        LpTokenEvents::votes(env, to, previous, votes);
+    }
+    Ok(())
+}
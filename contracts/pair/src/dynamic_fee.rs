@@ -1,6 +1,7 @@
 use crate::errors::PairError;
 use crate::fee_decay::apply_time_decay;
-use crate::storage::FeeState;
+use crate::math;
+use crate::storage::{FeeParams, FeeState};
 use soroban_sdk::Env;
 
 /// Fixed-point scale factor (1e14) — must match `math::SCALE`.
@@ -31,12 +32,9 @@ pub fn update_volatility(
     }
 
     // --- Size-weighted observation ------------------------------------------
-    // weight = trade_size * SCALE / total_reserve
-    let weight = trade_size
-        .checked_mul(SCALE)
-        .ok_or(PairError::Overflow)?
-        .checked_div(total_reserve)
-        .ok_or(PairError::Overflow)?;
+    // weight = trade_size * SCALE / total_reserve, widened since trade sizes
+    // on large pools overflow `i128` once scaled.
+    let weight = math::mul_div(env, trade_size, SCALE, total_reserve).ok_or(PairError::Overflow)?;
 
     // observation = price_delta_abs * weight / SCALE
     let observation = price_delta_abs
@@ -97,6 +95,51 @@ pub fn compute_fee_bps(fee_state: &FeeState) -> u32 {
     fee.clamp(fee_state.min_fee_bps as i128, fee_state.max_fee_bps as i128) as u32
 }
 
+/// Builds a fresh fee state from `params` with zero accumulated volatility.
+///
+/// `last_fee_update` starts at the current ledger sequence so that decay is
+/// measured from the moment the pair went live.
+pub fn new_fee_state(env: &Env, params: &FeeParams) -> FeeState {
+    let mut fee_state = FeeState {
+        vol_accumulator: 0,
+        ema_alpha: 0,
+        baseline_fee_bps: 0,
+        min_fee_bps: 0,
+        max_fee_bps: 0,
+        ramp_up_multiplier: 0,
+        cooldown_divisor: 0,
+        last_fee_update: env.ledger().sequence() as u64,
+        decay_threshold_blocks: 0,
+    };
+    apply_fee_params(&mut fee_state, params);
+    fee_state
+}
+
+/// Overwrites the tunable parameters of `fee_state`, keeping the volatility
+/// accumulator and its last-update marker intact.
+pub fn apply_fee_params(fee_state: &mut FeeState, params: &FeeParams) {
+    fee_state.ema_alpha = params.ema_alpha;
+    fee_state.baseline_fee_bps = params.baseline_fee_bps;
+    fee_state.min_fee_bps = params.min_fee_bps;
+    fee_state.max_fee_bps = params.max_fee_bps;
+    fee_state.ramp_up_multiplier = params.ramp_up_multiplier;
+    fee_state.cooldown_divisor = params.cooldown_divisor;
+    fee_state.decay_threshold_blocks = params.decay_threshold_blocks;
+}
+
+/// Extracts the tunable parameters from `fee_state`.
+pub fn fee_params(fee_state: &FeeState) -> FeeParams {
+    FeeParams {
+        ema_alpha: fee_state.ema_alpha,
+        baseline_fee_bps: fee_state.baseline_fee_bps,
+        min_fee_bps: fee_state.min_fee_bps,
+        max_fee_bps: fee_state.max_fee_bps,
+        ramp_up_multiplier: fee_state.ramp_up_multiplier,
+        cooldown_divisor: fee_state.cooldown_divisor,
+        decay_threshold_blocks: fee_state.decay_threshold_blocks,
+    }
+}
+
 /// Decays the volatility accumulator if the pool has been idle.
 pub fn decay_stale_ema(env: &Env, fee_state: &mut FeeState) {
     let current_ledger = env.ledger().sequence() as u64;
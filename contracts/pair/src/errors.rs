@@ -19,4 +19,6 @@ pub enum PairError {
     InsufficientLiquidityMinted = 112,
     InsufficientLiquidityBurned = 113,
     InvalidInput = 114,
+    ObservationTooOld = 115,
+    UnsupportedStorageVersion = 116,
 }
@@ -38,6 +38,10 @@ impl PairEvents {
         env.events().publish((symbol_short!("sync"),), (reserve_a, reserve_b));
     }
 
+    pub fn paused_set(env: &Env, paused: bool) {
+        env.events().publish((symbol_short!("paused"),), paused);
+    }
+
     // Emits a `flash_loan` event after a successful flash loan.
 
     // Topics: `("pair", "flash_loan")`
@@ -5,7 +5,7 @@ use coralswap_flash_receiver_interface::FlashReceiverClient;
 use crate::{
     errors::PairError,
     events::PairEvents,
-    reentrancy,
+    math, oracle, pause, reentrancy,
     storage::{get_fee_state, get_pair_state, set_pair_state},
 };
 
@@ -48,7 +48,8 @@ pub fn compute_flash_fee(amount: i128, current_fee_bps: u32) -> i128 {
 ///    repay principal + fee before the callback returns.
 /// 5. **Repayment check** — `new_balance >= old_reserve + fee` for each
 ///    borrowed token.
-/// 6. **Reserve update** — set reserves to post-callback balances.
+/// 6. **Reserve update** — accrue the TWAP oracle, then set reserves to
+///    post-callback balances.
 /// 7. **k-invariant** — `post_k >= pre_k`; reverts on violation.
 /// 8. **Persist + emit** — write updated state, publish event.
 /// 9. **Release lock**.
@@ -59,6 +60,7 @@ pub fn compute_flash_fee(amount: i128, current_fee_bps: u32) -> i128 {
 /// | `FlashPayloadTooLarge`   | `data.len() > MAX_PAYLOAD_SIZE` (256 bytes)       |
 /// | `InsufficientInputAmount`| Both amounts are zero, or either is negative      |
 /// | `NotInitialized`         | Pair storage not yet written by `initialize`       |
+/// | `Paused`                 | The pair or the factory is paused                 |
 /// | `InsufficientLiquidity`  | Requested amount exceeds current reserves         |
 /// | `Locked`                 | Reentrancy — another flash loan is in progress    |
 /// | `FlashLoanNotRepaid`     | Post-callback balance < `old_reserve + fee`       |
@@ -93,6 +95,7 @@ pub fn execute_flash_loan(
     // -----------------------------------------------------------------------
 
     let mut state = get_pair_state(env).ok_or(PairError::NotInitialized)?;
+    pause::require_not_paused(env, &state)?;
 
     // Requested amounts must not exceed current reserves.
     if amount_a > state.reserve_a || amount_b > state.reserve_b {
@@ -100,7 +103,7 @@ pub fn execute_flash_loan(
     }
 
     // Snapshot pre-loan constant-product invariant k = reserve_a * reserve_b.
-    let pre_k = state.reserve_a.checked_mul(state.reserve_b).ok_or(PairError::Overflow)?;
+    let pre_k = math::mul_wide(env, state.reserve_a, state.reserve_b).ok_or(PairError::Overflow)?;
 
     // -----------------------------------------------------------------------
     // 3. Reentrancy guard — first state write
@@ -179,6 +182,7 @@ pub fn execute_flash_loan(
     // 8. Reserve update
     // -----------------------------------------------------------------------
 
+    oracle::update(env, &mut state);
     state.reserve_a = new_balance_a;
     state.reserve_b = new_balance_b;
 
@@ -187,14 +191,13 @@ pub fn execute_flash_loan(
     // -----------------------------------------------------------------------
 
     // post_k must be >= pre_k; the fee income ensures this when repaid.
-    let post_k = state.reserve_a.checked_mul(state.reserve_b).ok_or(PairError::Overflow)?;
+    let post_k =
+        math::mul_wide(env, state.reserve_a, state.reserve_b).ok_or(PairError::Overflow)?;
 
     if post_k < pre_k {
         return Err(PairError::InvalidK);
     }
 
-    state.k_last = post_k;
-
     // -----------------------------------------------------------------------
     // 10. Persist updated reserves and emit event
     // -----------------------------------------------------------------------
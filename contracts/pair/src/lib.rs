@@ -10,6 +10,8 @@ mod fee_decay;
 mod flash_loan;
 mod math;
 mod oracle;
+mod pause;
+mod protocol_fee;
 mod reentrancy;
 mod storage;
 
@@ -20,14 +22,26 @@ use errors::PairError;
 use events::PairEvents;
 use math::MINIMUM_LIQUIDITY;
 use soroban_sdk::{
-    contract, contractclient, contractimpl, token::TokenClient, Address, Bytes, Env,
+    contract, contractclient, contractimpl, token::TokenClient, Address, Bytes, BytesN, Env, Vec,
+    U256,
+};
+use storage::{
+    get_fee_state, get_observation_state, get_pair_state, set_fee_state, set_pair_state, FeeParams,
+    ObservationState,
 };
-use storage::{get_fee_state, get_pair_state, set_fee_state, set_pair_state};
 
 #[contractclient(name = "LpTokenClient")]
 pub trait LpTokenInterface {
     fn mint(env: Env, to: Address, amount: i128);
     fn total_supply(env: Env) -> i128;
+    fn bump(env: Env);
+}
+
+#[contractclient(name = "FactoryClient")]
+pub trait FactoryInterface {
+    fn fee_to(env: Env) -> Option<Address>;
+    fn protocol_fee_share_bps(env: Env) -> u32;
+    fn is_paused(env: Env) -> bool;
 }
 
 #[contract]
@@ -49,6 +63,7 @@ impl Pair {
     /// * `token_a` - The address of the first token in the pair
     /// * `token_b` - The address of the second token in the pair
     /// * `lp_token` - The address of the LP (liquidity provider) token
+    /// * `fee_params` - Dynamic fee parameters, normally the factory defaults
     ///
     /// # Returns
     /// * `Ok(())` - If initialization was successful
@@ -65,6 +80,7 @@ impl Pair {
     ///     token_a_address,
     ///     token_b_address,
     ///     lp_token_address,
+    ///     fee_params,
     /// );
     /// assert_eq!(result, Ok(()));
     /// ```
@@ -74,6 +90,7 @@ impl Pair {
         token_a: Address,
         token_b: Address,
         lp_token: Address,
+        fee_params: FeeParams,
     ) -> Result<(), PairError> {
         if get_pair_state(&env).is_some() {
             return Err(PairError::AlreadyInitialized);
@@ -88,12 +105,135 @@ impl Pair {
             block_timestamp_last: env.ledger().timestamp(),
             price_a_cumulative: 0,
             price_b_cumulative: 0,
-            k_last: 0,
+            k_last: U256::from_u32(&env, 0),
         };
         set_pair_state(&env, &state);
+        set_fee_state(&env, &dynamic_fee::new_fee_state(&env, &fee_params));
+        oracle::observations::initialize(&env, &state);
+        storage::set_storage_version(&env, storage::STORAGE_VERSION);
+        Ok(())
+    }
+
+    /// Replaces this pair's code with `new_wasm_hash`. Only callable by the factory.
+    ///
+    /// The new code takes effect from the next invocation; the factory follows
+    /// up with [`Pair::migrate`] so stored data matches the new layout.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the code was replaced
+    /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        state.factory.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
 
+    /// Migrates stored data to the layout this code expects.
+    ///
+    /// Idempotent and permissionless: it only ever rewrites data into the
+    /// shape the running code already requires, so anyone may complete a
+    /// migration the factory started.
+    ///
+    /// # Returns
+    /// * `Ok(version)` - The storage version now in effect
+    /// * `Err(PairError::UnsupportedStorageVersion)` - If stored data is newer than this code
+    pub fn migrate(env: Env) -> Result<u32, PairError> {
+        // Only check for presence: older layouts don't decode as `PairStorage`.
+        if !storage::has_pair_state(&env) {
+            return Err(PairError::NotInitialized);
+        }
+        storage::migrate(&env)
+    }
+
+    /// Returns the layout version of this pair's stored data.
+    pub fn storage_version(env: Env) -> u32 {
+        storage::get_storage_version(&env)
+    }
+
+    /// Extends the TTL of the pair's instance storage, its newest observation
+    /// and its LP token's instance storage.
+    ///
+    /// Permissionless, so keepers can keep idle pools from being archived.
+    /// Active pools extend themselves on every state-changing call. The rest
+    /// of the observation ring buffer is extended in pages with
+    /// `bump_observations`.
+    pub fn bump(env: Env) -> Result<(), PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        let cursor = get_observation_state(&env).ok_or(PairError::NotInitialized)?;
+        storage::extend_instance_ttl(&env);
+        storage::extend_observation_ttl(&env, cursor.index);
+        LpTokenClient::new(&env, &state.lp_token).bump();
+        Ok(())
+    }
+
+    /// Extends the TTL of up to `limit` observation slots starting at `start`.
+    /// Permissionless.
+    ///
+    /// `limit` is capped at 32 slots per call, so a full 1024-slot buffer is
+    /// kept alive over several calls.
+    ///
+    /// # Returns
+    /// * `Ok(next)` - The slot to resume from; equals `cardinality_next` once done
+    pub fn bump_observations(env: Env, start: u32, limit: u32) -> Result<u32, PairError> {
+        oracle::observations::bump(&env, start, limit)
+    }
+
+    /// Replaces the pair's dynamic fee parameters. Only callable by the factory.
+    ///
+    /// The volatility accumulator is preserved, so the new parameters take
+    /// effect from the next swap without resetting accumulated market state.
+    /// Bounds are validated by the factory before it calls in.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `fee_params` - The new dynamic fee parameters
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the parameters were updated
+    /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    pub fn set_fee_params(env: Env, fee_params: FeeParams) -> Result<(), PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        state.factory.require_auth();
+
+        let mut fee_state = get_fee_state(&env).ok_or(PairError::NotInitialized)?;
+        dynamic_fee::apply_fee_params(&mut fee_state, &fee_params);
+        set_fee_state(&env, &fee_state);
+        Ok(())
+    }
+
+    /// Pauses or unpauses this pair alone. Only callable by the factory.
+    ///
+    /// Independent of the factory-wide pause: the pair is effectively paused
+    /// while either flag is set. See [`Pair::is_paused`].
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the flag was updated
+    /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    pub fn set_paused(env: Env, paused: bool) -> Result<(), PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        state.factory.require_auth();
+
+        storage::set_pair_paused(&env, paused);
+        PairEvents::paused_set(&env, paused);
+        Ok(())
+    }
+
+    /// Returns whether the pair is effectively paused, i.e. paused on its own
+    /// or through the factory. While paused, `mint`, `swap` and `flash_loan`
+    /// revert with `Paused`; `burn` stays open.
+    pub fn is_paused(env: Env) -> Result<bool, PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        Ok(pause::is_paused(&env, &state))
+    }
+
+    /// Returns the pair's current dynamic fee parameters.
+    pub fn fee_params(env: Env) -> Result<FeeParams, PairError> {
+        let fee_state = get_fee_state(&env).ok_or(PairError::NotInitialized)?;
+        Ok(dynamic_fee::fee_params(&fee_state))
+    }
+
     /// Mints LP tokens when liquidity is deposited into the pair.
     ///
     /// The caller must have already transferred both token_a and token_b to this contract
@@ -108,6 +248,7 @@ impl Pair {
     /// # Returns
     /// * `Ok(liquidity)` - The amount of LP tokens minted
     /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    /// * `Err(PairError::Paused)` - If the pair or the factory is paused
     /// * `Err(PairError::InsufficientLiquidityMinted)` - If the computed liquidity amount is zero or negative
     /// * `Err(PairError::Overflow)` - If arithmetic operations overflow
     ///
@@ -126,6 +267,7 @@ impl Pair {
         to.require_auth();
 
         let mut state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        pause::require_not_paused(&env, &state)?;
         let contract = env.current_contract_address();
 
         let balance_a = TokenClient::new(&env, &state.token_a).balance(&contract);
@@ -133,22 +275,26 @@ impl Pair {
         let amount_a = balance_a - state.reserve_a;
         let amount_b = balance_b - state.reserve_b;
 
+        // Protocol fee must be minted before reading supply so new LPs are
+        // not diluted by fees accrued before their deposit.
+        let fee_on = protocol_fee::mint_fee(&env, &mut state)?;
+
         let lp_client = LpTokenClient::new(&env, &state.lp_token);
         let total_supply = lp_client.total_supply();
 
         let liquidity;
         if total_supply == 0 {
-            liquidity = math::sqrt(amount_a.checked_mul(amount_b).ok_or(PairError::Overflow)?)
-                - MINIMUM_LIQUIDITY;
+            let product = math::mul_wide(&env, amount_a, amount_b).ok_or(PairError::Overflow)?;
+            liquidity = math::sqrt_wide(&env, &product) - MINIMUM_LIQUIDITY;
             if liquidity <= 0 {
                 return Err(PairError::InsufficientLiquidityMinted);
             }
             lp_client.mint(&contract, &MINIMUM_LIQUIDITY);
         } else {
-            let liquidity_a =
-                amount_a.checked_mul(total_supply).ok_or(PairError::Overflow)? / state.reserve_a;
-            let liquidity_b =
-                amount_b.checked_mul(total_supply).ok_or(PairError::Overflow)? / state.reserve_b;
+            let liquidity_a = math::mul_div(&env, amount_a, total_supply, state.reserve_a)
+                .ok_or(PairError::Overflow)?;
+            let liquidity_b = math::mul_div(&env, amount_b, total_supply, state.reserve_b)
+                .ok_or(PairError::Overflow)?;
             liquidity = liquidity_a.min(liquidity_b);
         }
 
@@ -158,9 +304,12 @@ impl Pair {
 
         lp_client.mint(&to, &liquidity);
 
+        oracle::update(&env, &mut state);
         state.reserve_a = balance_a;
         state.reserve_b = balance_b;
-        state.k_last = balance_a.checked_mul(balance_b).ok_or(PairError::Overflow)?;
+        if fee_on {
+            state.k_last = math::mul_wide(&env, balance_a, balance_b).ok_or(PairError::Overflow)?;
+        }
         set_pair_state(&env, &state);
 
         PairEvents::mint(&env, &to, amount_a, amount_b);
@@ -174,6 +323,10 @@ impl Pair {
     /// balance in the contract is burned, and the proportional amounts of both token_a
     /// and token_b are transferred to the `to` address.
     ///
+    /// As in Uniswap V2, `to` does not need to authorize the call: whoever sent the LP
+    /// tokens to the pair decides where the underlying goes. This lets the router
+    /// unwind a position funded through an allowance or a permit.
+    ///
     /// # Arguments
     /// * `env` - The Soroban environment
     /// * `to` - The recipient address for the withdrawn tokens
@@ -185,7 +338,6 @@ impl Pair {
     /// * `Err(PairError::Overflow)` - If arithmetic operations overflow
     ///
     /// # Panics
-    /// * If authentication from `to` address fails
     /// * If token operations (burn, transfer) fail
     /// * If reserve update fails
     ///
@@ -196,18 +348,18 @@ impl Pair {
     /// println!("Received {} token_a and {} token_b", amount_a, amount_b);
     /// ```
     pub fn burn(env: Env, to: Address) -> Result<(i128, i128), PairError> {
-        to.require_auth();
-
         let mut state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
         let contract = env.current_contract_address();
 
         let lp_balance = TokenClient::new(&env, &state.lp_token).balance(&contract);
+
+        let fee_on = protocol_fee::mint_fee(&env, &mut state)?;
         let total_supply = LpTokenClient::new(&env, &state.lp_token).total_supply();
 
-        let amount_a =
-            lp_balance.checked_mul(state.reserve_a).ok_or(PairError::Overflow)? / total_supply;
-        let amount_b =
-            lp_balance.checked_mul(state.reserve_b).ok_or(PairError::Overflow)? / total_supply;
+        let amount_a = math::mul_div(&env, lp_balance, state.reserve_a, total_supply)
+            .ok_or(PairError::Overflow)?;
+        let amount_b = math::mul_div(&env, lp_balance, state.reserve_b, total_supply)
+            .ok_or(PairError::Overflow)?;
 
         if amount_a <= 0 || amount_b <= 0 {
             return Err(PairError::InsufficientLiquidityBurned);
@@ -218,9 +370,13 @@ impl Pair {
         TokenClient::new(&env, &state.token_a).transfer(&contract, &to, &amount_a);
         TokenClient::new(&env, &state.token_b).transfer(&contract, &to, &amount_b);
 
+        oracle::update(&env, &mut state);
         state.reserve_a -= amount_a;
         state.reserve_b -= amount_b;
-        state.k_last = state.reserve_a.checked_mul(state.reserve_b).ok_or(PairError::Overflow)?;
+        if fee_on {
+            state.k_last = math::mul_wide(&env, state.reserve_a, state.reserve_b)
+                .ok_or(PairError::Overflow)?;
+        }
         set_pair_state(&env, &state);
 
         PairEvents::burn(&env, &to, amount_a, amount_b, &to);
@@ -246,6 +402,7 @@ impl Pair {
     /// # Returns
     /// * `Ok(())` - If the swap executed successfully
     /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    /// * `Err(PairError::Paused)` - If the pair or the factory is paused
     /// * `Err(PairError::InsufficientOutputAmount)` - If both output amounts are zero or result is invalid
     /// * `Err(PairError::InsufficientLiquidity)` - If requested output exceeds available reserves
     /// * `Err(PairError::InsufficientInputAmount)` - If no input tokens were transferred
@@ -293,6 +450,7 @@ impl Pair {
 
         // ── 3. Load state ─────────────────────────────────────────────────────
         let mut pair = get_pair_state(env).ok_or(PairError::NotInitialized)?;
+        pause::require_not_paused(env, &pair)?;
         let mut fee_state = get_fee_state(env).ok_or(PairError::NotInitialized)?;
 
         // ── 4. Check output vs reserves ───────────────────────────────────────
@@ -332,35 +490,28 @@ impl Pair {
         // ── 10. Fee-adjusted balances (Uniswap V2 K check) ───────────────────
         // balance_adjusted = balance * 10_000 - amount_in * fee_bps
         // This avoids floating point: multiply reserves by 10_000 so fee
-        // subtraction is exact.
-        let fee = fee_bps as i128;
-        let balance_a_adj = balance_a
-            .checked_mul(10_000)
-            .ok_or(PairError::Overflow)?
-            .checked_sub(amount_a_in * fee)
-            .ok_or(PairError::Overflow)?;
-        let balance_b_adj = balance_b
-            .checked_mul(10_000)
-            .ok_or(PairError::Overflow)?
-            .checked_sub(amount_b_in * fee)
-            .ok_or(PairError::Overflow)?;
-
-        if balance_a_adj <= 0 || balance_b_adj <= 0 {
-            return Err(PairError::InsufficientOutputAmount);
-        }
+        // subtraction is exact. Both terms are computed at 256 bits, so no
+        // balance the tokens can represent overflows.
+        let balance_a_adj = math::fee_adjusted(env, balance_a, amount_a_in, fee_bps)
+            .ok_or(PairError::InsufficientOutputAmount)?;
+        let balance_b_adj = math::fee_adjusted(env, balance_b, amount_b_in, fee_bps)
+            .ok_or(PairError::InsufficientOutputAmount)?;
 
         // ── 11. K-invariant check ─────────────────────────────────────────────
         // balance_a_adj * balance_b_adj >= reserve_a * reserve_b * 10_000^2
-        let k_before = pair
-            .reserve_a
-            .checked_mul(pair.reserve_b)
-            .ok_or(PairError::Overflow)?
-            .checked_mul(100_000_000) // 10_000^2
-            .ok_or(PairError::Overflow)?;
-
-        let k_after = balance_a_adj.checked_mul(balance_b_adj).ok_or(PairError::Overflow)?;
-
-        if k_after < k_before {
+        // Neither side is formed in full: near the i128 limit both exceed
+        // 256 bits.
+        let k_holds = math::product_covers(
+            env,
+            &balance_a_adj,
+            &balance_b_adj,
+            pair.reserve_a,
+            pair.reserve_b,
+            100_000_000, // 10_000^2
+        )
+        .ok_or(PairError::Overflow)?;
+
+        if !k_holds {
             return Err(PairError::InvalidK);
         }
 
@@ -370,9 +521,8 @@ impl Pair {
         let total_reserve = pair.reserve_a.saturating_add(pair.reserve_b);
         let trade_size = amount_a_in.max(amount_b_in);
         // Simple price delta proxy: change in effective reserve ratio.
-        let old_price =
-            if pair.reserve_a > 0 { (pair.reserve_b * 10_000) / pair.reserve_a } else { 0 };
-        let new_price = if balance_a > 0 { (balance_b * 10_000) / balance_a } else { 0 };
+        let old_price = math::mul_div(env, pair.reserve_b, 10_000, pair.reserve_a).unwrap_or(0);
+        let new_price = math::mul_div(env, balance_b, 10_000, balance_a).unwrap_or(0);
         let price_delta = (new_price - old_price).unsigned_abs() as i128;
 
         dynamic_fee::update_volatility(
@@ -383,11 +533,12 @@ impl Pair {
             total_reserve,
         )?;
 
-        // ── 13. Update K_last and reserves ────────────────────────────────────
-        pair.k_last = balance_a * balance_b;
+        // ── 13. Update reserves ───────────────────────────────────────────────
+        // `k_last` is only snapshotted on liquidity events so that the growth
+        // from swap fees can be measured by `protocol_fee::mint_fee`.
+        oracle::update(env, &mut pair);
         pair.reserve_a = balance_a;
         pair.reserve_b = balance_b;
-        pair.block_timestamp_last = env.ledger().timestamp();
 
         // ── 14. Persist state ─────────────────────────────────────────────────
         set_pair_state(env, &pair);
@@ -429,6 +580,7 @@ impl Pair {
     /// # Returns
     /// * `Ok(())` - If the flash loan was executed and repaid successfully
     /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    /// * `Err(PairError::Paused)` - If the pair or the factory is paused
     /// * `Err(PairError::InsufficientLiquidity)` - If requested amounts exceed available reserves
     /// * `Err(PairError::InvalidK)` - If reserves are invalid after repayment
     /// * `Err(PairError::Overflow)` - If fee calculations overflow
@@ -495,6 +647,10 @@ impl Pair {
     /// protection against slippage and MEV. A basis point (bps) is 1/100th of a percent,
     /// so a fee of 30 bps equals 0.3%.
     ///
+    /// Stale volatility is decayed exactly as `swap` does before charging, so the
+    /// returned value is the fee a swap in the current ledger would pay. The decay
+    /// is applied to a copy and never persisted by this view.
+    ///
     /// # Arguments
     /// * `env` - The Soroban environment
     ///
@@ -508,7 +664,109 @@ impl Pair {
     /// println!("Current swap fee: {} bps ({:.2}%)", fee_bps, fee_percent);
     /// ```
     pub fn get_current_fee_bps(env: Env) -> u32 {
-        get_fee_state(&env).map(|fs| dynamic_fee::compute_fee_bps(&fs)).unwrap_or(30)
+        get_fee_state(&env)
+            .map(|mut fs| {
+                dynamic_fee::decay_stale_ema(&env, &mut fs);
+                dynamic_fee::compute_fee_bps(&fs)
+            })
+            .unwrap_or(30)
+    }
+
+    /// Returns the cumulative prices accrued up to the current ledger timestamp.
+    ///
+    /// The accumulators are advanced counterfactually from the last stored
+    /// update using the current reserves, without writing to storage, so two
+    /// calls at different times can be differenced directly to obtain a TWAP:
+    /// `(cumulative_end - cumulative_start) / (t_end - t_start)`, scaled by 1e14.
+    /// Accumulators wrap on overflow; use wrapping subtraction when differencing.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    ///
+    /// # Returns
+    /// A tuple of `(price_a_cumulative, price_b_cumulative, timestamp)` where:
+    /// * `price_a_cumulative` - Time-integral of token_a's price in token_b
+    /// * `price_b_cumulative` - Time-integral of token_b's price in token_a
+    /// * `timestamp` - The ledger timestamp the accumulators are accrued to
+    ///
+    /// # Example
+    /// ```ignore
+    /// let (p0, _, t0) = Pair::get_cumulative_prices(env.clone())?;
+    /// // ... 30 minutes later ...
+    /// let (p1, _, t1) = Pair::get_cumulative_prices(env)?;
+    /// let twap_a = p1.wrapping_sub(p0) / (t1 - t0) as i128;
+    /// ```
+    pub fn get_cumulative_prices(env: Env) -> Result<(i128, i128, u64), PairError> {
+        let mut state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        oracle::accrue(&env, &mut state);
+        Ok((state.price_a_cumulative, state.price_b_cumulative, state.block_timestamp_last))
+    }
+
+    /// Returns the cumulative prices as of each `seconds_ago` before now.
+    ///
+    /// Reads from the observation ring buffer, interpolating between recorded
+    /// observations and extrapolating past the newest one with the current
+    /// reserves. Differencing two results and dividing by the gap in seconds
+    /// yields a TWAP scaled by 1e14; see `consult` for the one-call version.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `seconds_ago` - Offsets into the past, in seconds, to read at
+    ///
+    /// # Returns
+    /// * `Ok(cumulatives)` - `(price_a_cumulative, price_b_cumulative)` per offset
+    /// * `Err(PairError::ObservationTooOld)` - If an offset predates the oldest observation
+    /// * `Err(PairError::NotInitialized)` - If the pair has not been initialized
+    pub fn observe(env: Env, seconds_ago: Vec<u64>) -> Result<Vec<(i128, i128)>, PairError> {
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        oracle::observations::observe(&env, &state, &seconds_ago)
+    }
+
+    /// Returns the time-weighted average prices over the last `window` seconds.
+    ///
+    /// # Returns
+    /// * `Ok((twap_a, twap_b))` - Average price of each token in the other, scaled by 1e14
+    /// * `Err(PairError::InvalidInput)` - If `window` is zero
+    /// * `Err(PairError::ObservationTooOld)` - If the buffer does not reach back `window` seconds
+    ///
+    /// # Example
+    /// ```ignore
+    /// // 30-minute TWAP of token_a denominated in token_b
+    /// let (twap_a, _) = Pair::consult(env, 1_800)?;
+    /// ```
+    pub fn consult(env: Env, window: u64) -> Result<(i128, i128), PairError> {
+        if window == 0 {
+            return Err(PairError::InvalidInput);
+        }
+        let state = get_pair_state(&env).ok_or(PairError::NotInitialized)?;
+        let points =
+            oracle::observations::observe(&env, &state, &Vec::from_array(&env, [window, 0]))?;
+        let (start_a, start_b) = points.get_unchecked(0);
+        let (end_a, end_b) = points.get_unchecked(1);
+        Ok((
+            oracle::consult_twap(start_a, end_a, window)?,
+            oracle::consult_twap(start_b, end_b, window)?,
+        ))
+    }
+
+    /// Grows the observation ring buffer towards `cardinality_next` entries.
+    /// Permissionless: the caller pays for the pre-allocated slots.
+    ///
+    /// Growth is incremental: one call adds at most 32 slots, so callers
+    /// repeat until the returned capacity reaches `cardinality_next`. New
+    /// capacity enters rotation once the buffer next wraps, so history
+    /// lengthens gradually as observations are written.
+    ///
+    /// # Returns
+    /// * `Ok(cardinality_next)` - The resulting target capacity
+    /// * `Err(PairError::InvalidInput)` - If `cardinality_next` exceeds the maximum of 1024
+    pub fn grow_observations(env: Env, cardinality_next: u32) -> Result<u32, PairError> {
+        oracle::observations::grow(&env, cardinality_next)
+    }
+
+    /// Returns the observation ring buffer's cursor and capacity.
+    pub fn observation_state(env: Env) -> Result<ObservationState, PairError> {
+        get_observation_state(&env).ok_or(PairError::NotInitialized)
     }
 
     pub fn lp_token(env: Env) -> Result<Address, PairError> {
@@ -548,35 +806,11 @@ impl Pair {
         let balance_b = TokenClient::new(&env, &state.token_b).balance(&contract);
 
         // ── Update cumulative price accumulators ──────────────────────────────
-        let current_timestamp = env.ledger().timestamp();
-        let time_elapsed = current_timestamp.saturating_sub(state.block_timestamp_last) as i128;
-
-        if time_elapsed > 0 && state.reserve_a > 0 && state.reserve_b > 0 {
-            // price_a_cumulative += (reserve_b / reserve_a) * time_elapsed
-            // Using integer division: (reserve_b * time_elapsed) / reserve_a
-            let price_a_delta = state
-                .reserve_b
-                .checked_mul(time_elapsed)
-                .ok_or(PairError::Overflow)?
-                .checked_div(state.reserve_a)
-                .ok_or(PairError::Overflow)?;
-            state.price_a_cumulative = state.price_a_cumulative.checked_add(price_a_delta).ok_or(PairError::Overflow)?;
-
-            // price_b_cumulative += (reserve_a / reserve_b) * time_elapsed
-            // Using integer division: (reserve_a * time_elapsed) / reserve_b
-            let price_b_delta = state
-                .reserve_a
-                .checked_mul(time_elapsed)
-                .ok_or(PairError::Overflow)?
-                .checked_div(state.reserve_b)
-                .ok_or(PairError::Overflow)?;
-            state.price_b_cumulative = state.price_b_cumulative.checked_add(price_b_delta).ok_or(PairError::Overflow)?;
-        }
+        oracle::update(&env, &mut state);
 
-        // ── Update reserves and timestamp ────────────────────────────────────
+        // ── Update reserves ──────────────────────────────────────────────────
         state.reserve_a = balance_a;
         state.reserve_b = balance_b;
-        state.block_timestamp_last = current_timestamp;
         set_pair_state(&env, &state);
         PairEvents::sync(&env, balance_a, balance_b);
         Ok(())
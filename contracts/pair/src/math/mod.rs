@@ -1,27 +1,122 @@
 //! Fixed-point arithmetic helpers for price and reserve calculations.
 //! All values use 1e14 scaling to maintain precision without floating point.
 
+use soroban_sdk::{Env, U256};
+
 /// Fixed-point scale factor.
-#[allow(dead_code)]
 pub const SCALE: i128 = 100_000_000_000_000; // 1e14
 /// Basis point denominator.
-#[allow(dead_code)]
 pub const BPS_DENOMINATOR: i128 = 10_000;
 /// Minimum liquidity locked on first mint to prevent division by zero.
 pub const MINIMUM_LIQUIDITY: i128 = 1_000;
 
-/// Multiplied two scaled values and divided by SCALE to maintain precision.
-#[allow(dead_code)]
-pub fn mul_div(a: i128, b: i128, denominator: i128) -> Option<i128> {
-    if denominator == 0 {
+/// Lifts a non-negative `i128` into a `U256`. Returns `None` for negatives.
+pub fn to_u256(env: &Env, value: i128) -> Option<U256> {
+    if value < 0 {
         return None;
     }
-    // Use u256 intermediate to avoid overflow on large reserves.
-    // TODO: implement with soroban U256 type
-    Some((a * b) / denominator)
+    Some(U256::from_u128(env, value as u128))
+}
+
+/// Narrows a `U256` back to `i128`. Returns `None` if it does not fit.
+pub fn from_u256(value: &U256) -> Option<i128> {
+    value.to_u128().and_then(|v| i128::try_from(v).ok())
+}
+
+/// Computes the full 256-bit product `a * b` of two non-negative values.
+///
+/// Used for reserve products and the k-invariant, which exceed `i128` for
+/// pools of large 18-decimal tokens.
+pub fn mul_wide(env: &Env, a: i128, b: i128) -> Option<U256> {
+    Some(to_u256(env, a)?.mul(&to_u256(env, b)?))
+}
+
+/// Computes `floor(a * b / denominator)` with a 256-bit intermediate.
+///
+/// Returns `None` for a zero denominator, negative operands, or a quotient
+/// that does not fit in `i128`.
+pub fn mul_div(env: &Env, a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    from_u256(&mul_wide(env, a, b)?.div(&to_u256(env, denominator)?))
+}
+
+/// Computes the fee-adjusted balance `balance * 10_000 - amount_in * fee_bps`
+/// at 256 bits, so neither term can overflow.
+///
+/// Returns `None` for a negative input or when the fee would leave nothing,
+/// i.e. the adjusted balance is not strictly positive.
+pub fn fee_adjusted(env: &Env, balance: i128, amount_in: i128, fee_bps: u32) -> Option<U256> {
+    let scaled = to_u256(env, balance)?.mul(&U256::from_u32(env, BPS_DENOMINATOR as u32));
+    let fee = to_u256(env, amount_in)?.mul(&U256::from_u32(env, fee_bps));
+    if fee >= scaled {
+        return None;
+    }
+    Some(scaled.sub(&fee))
+}
+
+/// Returns whether `a * b >= c * d * scale` without forming either full
+/// product, which can exceed 256 bits for fee-adjusted balances near the
+/// `i128` limit.
+///
+/// `a` and `b` must be below `2^160` (e.g. an `i128` times a `u32`); `c` and
+/// `d` must be non-negative.
+pub fn product_covers(env: &Env, a: &U256, b: &U256, c: i128, d: i128, scale: u32) -> Option<bool> {
+    let product = mul_wide(env, c, d)?;
+    let zero = U256::from_u32(env, 0);
+    if *b == zero {
+        return Some(product == zero);
+    }
+
+    // a * b >= p * scale  <=>  a >= ceil(p * scale / b), split as
+    // p = q * b + r so every intermediate stays well inside 256 bits.
+    let quotient = product.div(b);
+    if quotient > *a {
+        return Some(false);
+    }
+    let scale = U256::from_u32(env, scale);
+    let remainder = product.rem_euclid(b).mul(&scale);
+    let mut needed = quotient.mul(&scale).add(&remainder.div(b));
+    if remainder.rem_euclid(b) != zero {
+        needed = needed.add(&U256::from_u32(env, 1));
+    }
+    Some(*a >= needed)
+}
+
+/// Computes `floor(sqrt(value))` for a 256-bit value.
+///
+/// Any product of two `i128`s has a root below `2^127`, so the result always
+/// fits. Newton's method is seeded with a power of two at or above the root,
+/// which keeps the number of host-side U256 operations small.
+pub fn sqrt_wide(env: &Env, value: &U256) -> i128 {
+    let zero = U256::from_u32(env, 0);
+    if *value == zero {
+        return 0;
+    }
+
+    let bytes = value.to_be_bytes();
+    let mut bits = 256u32;
+    for byte in bytes.iter() {
+        if byte != 0 {
+            bits -= byte.leading_zeros();
+            break;
+        }
+        bits -= 8;
+    }
+
+    let two = U256::from_u32(env, 2);
+    let mut x = U256::from_u32(env, 1).shl(bits.div_ceil(2));
+    let mut y = x.add(&value.div(&x)).div(&two);
+    while y < x {
+        x = y;
+        y = x.add(&value.div(&x)).div(&two);
+    }
+    from_u256(&x).unwrap_or(i128::MAX)
 }
 
 /// Computed integer square root using Newton's method.
+#[allow(dead_code)]
 pub fn sqrt(value: i128) -> i128 {
     if value <= 0 {
         return 0;
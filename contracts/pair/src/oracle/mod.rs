@@ -1,31 +1,107 @@
 use soroban_sdk::Env;
 
+use crate::{
+    errors::PairError,
+    math::{self, SCALE},
+    storage::PairStorage,
+};
+
+pub mod observations;
+
 // Cumulative price oracle (TWAP support).
 // Tracks cumulative token prices for time-weighted average price queries.
+//
+// Prices are fixed-point with `math::SCALE` (1e14) precision so that pools
+// with very unequal reserves still produce a meaningful TWAP. Accumulators
+// wrap on overflow, as in Uniswap V2: consumers only ever look at the
+// difference between two observations, which `consult_twap` computes with
+// wrapping subtraction. Accrual never fails, so the oracle cannot block the
+// reserve-changing operations that drive it.
 
 /// Updated cumulative price accumulators with current reserves.
 /// Called during every swap and liquidity event.
-#[allow(dead_code)]
+///
+/// ```text
+/// price_a_cumulative += (reserve_b * SCALE / reserve_a) * time_elapsed
+/// price_b_cumulative += (reserve_a * SCALE / reserve_b) * time_elapsed
+/// ```
+///
+/// Nothing accrues while either reserve is empty or no time has passed.
 pub fn update_cumulative_prices(
-    _env: &Env,
-    _reserve_a: i128,
-    _reserve_b: i128,
-    _time_elapsed: u64,
-    _price_a_cumulative: &mut i128,
-    _price_b_cumulative: &mut i128,
+    env: &Env,
+    reserve_a: i128,
+    reserve_b: i128,
+    time_elapsed: u64,
+    price_a_cumulative: &mut i128,
+    price_b_cumulative: &mut i128,
 ) {
-    // price_a_cumulative += (reserve_b / reserve_a) * time_elapsed
-    // price_b_cumulative += (reserve_a / reserve_b) * time_elapsed
-    todo!()
+    if time_elapsed == 0 || reserve_a <= 0 || reserve_b <= 0 {
+        return;
+    }
+
+    let elapsed = time_elapsed as i128;
+    let (price_a, price_b) = spot_prices(env, reserve_a, reserve_b);
+
+    *price_a_cumulative = price_a_cumulative.wrapping_add(price_a.wrapping_mul(elapsed));
+    *price_b_cumulative = price_b_cumulative.wrapping_add(price_b.wrapping_mul(elapsed));
+}
+
+/// Spot prices of each token in terms of the other, scaled by `math::SCALE`.
+///
+/// A price that does not fit in an `i128`, which takes reserves more than
+/// ~1.7e24 to 1 apart, saturates at `i128::MAX` instead of failing.
+///
+/// Callers must ensure both reserves are positive.
+pub fn spot_prices(env: &Env, reserve_a: i128, reserve_b: i128) -> (i128, i128) {
+    let price = |numerator, denominator| {
+        math::mul_div(env, numerator, SCALE, denominator).unwrap_or(i128::MAX)
+    };
+    (price(reserve_b, reserve_a), price(reserve_a, reserve_b))
+}
+
+/// Accrues `state`'s cumulative prices up to the current ledger timestamp.
+///
+/// Must be called with the reserves that were in effect since
+/// `block_timestamp_last`, i.e. *before* a reserve-changing operation writes
+/// the new reserves. Stamps `block_timestamp_last` with the current time.
+pub fn accrue(env: &Env, state: &mut PairStorage) {
+    let now = env.ledger().timestamp();
+    let time_elapsed = now.saturating_sub(state.block_timestamp_last);
+
+    update_cumulative_prices(
+        env,
+        state.reserve_a,
+        state.reserve_b,
+        time_elapsed,
+        &mut state.price_a_cumulative,
+        &mut state.price_b_cumulative,
+    );
+    state.block_timestamp_last = now;
+}
+
+/// Accrues `state` and records the result in the observation ring buffer.
+///
+/// This is what every reserve-changing entrypoint calls; `accrue` on its own
+/// is reserved for read-only views that project the accumulators forward.
+pub fn update(env: &Env, state: &mut PairStorage) {
+    accrue(env, state);
+    observations::write(env, state);
 }
 
 /// Consulted the cumulative price to compute TWAP over a period.
-#[allow(dead_code)]
+///
+/// Returns the average price over the window, scaled by `math::SCALE`.
+///
+/// ```text
+/// twap = (cumulative_end - cumulative_start) / time_elapsed
+/// ```
 pub fn consult_twap(
-    _price_cumulative_start: i128,
-    _price_cumulative_end: i128,
-    _time_elapsed: u64,
-) -> i128 {
-    // twap = (cumulative_end - cumulative_start) / time_elapsed
-    todo!()
+    price_cumulative_start: i128,
+    price_cumulative_end: i128,
+    time_elapsed: u64,
+) -> Result<i128, PairError> {
+    if time_elapsed == 0 {
+        return Err(PairError::InvalidInput);
+    }
+    Ok(price_cumulative_end.wrapping_sub(price_cumulative_start) / time_elapsed as i128)
 }
@@ -0,0 +1,241 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::{
+    errors::PairError,
+    oracle::spot_prices,
+    storage::{
+        extend_observation_ttl, get_observation, get_observation_state, set_observation,
+        set_observation_state, Observation, ObservationState, PairStorage,
+    },
+};
+
+// Observation ring buffer.
+//
+// Modelled on Uniswap V3's oracle: every reserve-changing operation records
+// the accumulators at most once per ledger timestamp, into a fixed-size ring
+// buffer held in persistent storage. Integrators can then read historic
+// cumulatives with `observe` instead of having to snapshot them off-chain.
+//
+// The buffer starts with a single slot. Anyone may pay to `grow` it; new slots
+// are pre-written as uninitialized placeholders so the storage cost falls on
+// whoever asked for the extra history rather than on the next swapper, and
+// only enter rotation once the cursor wraps back to the end of the old range.
+// Growth is incremental: each call pre-writes at most `MAX_OBSERVATION_BATCH`
+// slots, so reaching a large capacity takes several calls.
+
+/// Upper bound on ring buffer capacity, to keep `observe` binary searches cheap.
+pub const MAX_CARDINALITY: u32 = 1_024;
+
+/// Maximum number of observation slots `grow` pre-writes or `bump` extends in
+/// one call, keeping each call well within the per-transaction ledger
+/// footprint.
+pub const MAX_OBSERVATION_BATCH: u32 = 32;
+
+/// Seeds slot 0 with `state`'s accumulators as of its last update.
+pub fn initialize(env: &Env, state: &PairStorage) {
+    set_observation(
+        env,
+        0,
+        &Observation {
+            timestamp: state.block_timestamp_last,
+            price_a_cumulative: state.price_a_cumulative,
+            price_b_cumulative: state.price_b_cumulative,
+            initialized: true,
+        },
+    );
+    set_observation_state(env, &ObservationState { index: 0, cardinality: 1, cardinality_next: 1 });
+}
+
+/// Records `state`'s accumulators, unless an observation already exists for
+/// the current ledger timestamp.
+///
+/// Must be called after `oracle::accrue` and before the new reserves are
+/// written, so the observation reflects the price in effect up to now.
+pub fn write(env: &Env, state: &PairStorage) {
+    let Some(mut cursor) = get_observation_state(env) else {
+        return;
+    };
+    let now = env.ledger().timestamp();
+    if let Some(last) = get_observation(env, cursor.index) {
+        if last.timestamp == now {
+            return;
+        }
+    }
+
+    // Bring pre-allocated slots into rotation once the cursor reaches the end
+    // of the current range.
+    if cursor.cardinality_next > cursor.cardinality && cursor.index == cursor.cardinality - 1 {
+        cursor.cardinality = cursor.cardinality_next;
+    }
+    cursor.index = (cursor.index + 1) % cursor.cardinality;
+
+    set_observation(
+        env,
+        cursor.index,
+        &Observation {
+            timestamp: now,
+            price_a_cumulative: state.price_a_cumulative,
+            price_b_cumulative: state.price_b_cumulative,
+            initialized: true,
+        },
+    );
+    set_observation_state(env, &cursor);
+}
+
+/// Raises the buffer's target capacity towards `cardinality_next`,
+/// pre-allocating at most `MAX_OBSERVATION_BATCH` new slots. Requests at or
+/// below the current target are a no-op.
+///
+/// Returns the resulting target capacity; callers repeat until it reaches
+/// `cardinality_next`.
+pub fn grow(env: &Env, cardinality_next: u32) -> Result<u32, PairError> {
+    if cardinality_next > MAX_CARDINALITY {
+        return Err(PairError::InvalidInput);
+    }
+    let mut cursor = get_observation_state(env).ok_or(PairError::NotInitialized)?;
+    if cardinality_next <= cursor.cardinality_next {
+        return Ok(cursor.cardinality_next);
+    }
+
+    let cardinality_next = cardinality_next.min(cursor.cardinality_next + MAX_OBSERVATION_BATCH);
+    for index in cursor.cardinality_next..cardinality_next {
+        set_observation(
+            env,
+            index,
+            &Observation {
+                timestamp: 0,
+                price_a_cumulative: 0,
+                price_b_cumulative: 0,
+                initialized: false,
+            },
+        );
+    }
+    cursor.cardinality_next = cardinality_next;
+    set_observation_state(env, &cursor);
+    Ok(cardinality_next)
+}
+
+/// Extends the TTL of up to `limit` allocated slots starting at `start`,
+/// including pre-allocated ones not yet in rotation. `limit` is capped at
+/// `MAX_OBSERVATION_BATCH`.
+///
+/// Returns the slot to resume from, which reaches `cardinality_next` once
+/// every slot has been extended.
+pub fn bump(env: &Env, start: u32, limit: u32) -> Result<u32, PairError> {
+    let cursor = get_observation_state(env).ok_or(PairError::NotInitialized)?;
+    let end = start.saturating_add(limit.min(MAX_OBSERVATION_BATCH)).min(cursor.cardinality_next);
+    for index in start..end {
+        extend_observation_ttl(env, index);
+    }
+    Ok(end.max(start))
+}
+
+/// Returns `(price_a_cumulative, price_b_cumulative)` as of each
+/// `seconds_ago` before the current ledger timestamp.
+///
+/// Points between two observations are linearly interpolated, which is exact
+/// because the price is constant between reserve changes. Points after the
+/// newest observation are extrapolated with the current reserves.
+pub fn observe(
+    env: &Env,
+    state: &PairStorage,
+    seconds_ago: &Vec<u64>,
+) -> Result<Vec<(i128, i128)>, PairError> {
+    let cursor = get_observation_state(env).ok_or(PairError::NotInitialized)?;
+    let now = env.ledger().timestamp();
+
+    let mut cumulatives = Vec::new(env);
+    for ago in seconds_ago.iter() {
+        let target = now.checked_sub(ago).ok_or(PairError::ObservationTooOld)?;
+        cumulatives.push_back(observe_single(env, state, &cursor, target)?);
+    }
+    Ok(cumulatives)
+}
+
+fn observe_single(
+    env: &Env,
+    state: &PairStorage,
+    cursor: &ObservationState,
+    target: u64,
+) -> Result<(i128, i128), PairError> {
+    let newest = load(env, cursor.index)?;
+    if target >= newest.timestamp {
+        return Ok(extrapolate(env, &newest, state, target));
+    }
+
+    let mut oldest = load(env, (cursor.index + 1) % cursor.cardinality)?;
+    if !oldest.initialized {
+        // The ring has not wrapped yet, so slot 0 is the oldest.
+        oldest = load(env, 0)?;
+    }
+    if target < oldest.timestamp {
+        return Err(PairError::ObservationTooOld);
+    }
+
+    let (before, after) = binary_search(env, cursor, target)?;
+    if target == before.timestamp {
+        return Ok((before.price_a_cumulative, before.price_b_cumulative));
+    }
+    if target == after.timestamp {
+        return Ok((after.price_a_cumulative, after.price_b_cumulative));
+    }
+
+    let span = (after.timestamp - before.timestamp) as i128;
+    let offset = (target - before.timestamp) as i128;
+    let interpolate =
+        |start: i128, end: i128| start.wrapping_add(end.wrapping_sub(start) / span * offset);
+    Ok((
+        interpolate(before.price_a_cumulative, after.price_a_cumulative),
+        interpolate(before.price_b_cumulative, after.price_b_cumulative),
+    ))
+}
+
+/// Projects `newest` forward to `target` using the current reserves, which
+/// have been in effect since `newest` was written.
+fn extrapolate(env: &Env, newest: &Observation, state: &PairStorage, target: u64) -> (i128, i128) {
+    let elapsed = (target - newest.timestamp) as i128;
+    if elapsed == 0 || state.reserve_a <= 0 || state.reserve_b <= 0 {
+        return (newest.price_a_cumulative, newest.price_b_cumulative);
+    }
+    let (price_a, price_b) = spot_prices(env, state.reserve_a, state.reserve_b);
+    (
+        newest.price_a_cumulative.wrapping_add(price_a.wrapping_mul(elapsed)),
+        newest.price_b_cumulative.wrapping_add(price_b.wrapping_mul(elapsed)),
+    )
+}
+
+/// Finds the observations immediately at-or-before and at-or-after `target`.
+///
+/// Callers must have checked that `target` lies within
+/// `[oldest.timestamp, newest.timestamp)`.
+fn binary_search(
+    env: &Env,
+    cursor: &ObservationState,
+    target: u64,
+) -> Result<(Observation, Observation), PairError> {
+    let mut low = (cursor.index + 1) % cursor.cardinality;
+    let mut high = low + cursor.cardinality - 1;
+
+    loop {
+        let mid = (low + high) / 2;
+        let before = load(env, mid % cursor.cardinality)?;
+        if !before.initialized {
+            low = mid + 1;
+            continue;
+        }
+        let after = load(env, (mid + 1) % cursor.cardinality)?;
+
+        if before.timestamp <= target {
+            if target <= after.timestamp {
+                return Ok((before, after));
+            }
+            low = mid + 1;
+        } else {
+            high = mid - 1;
+        }
+    }
+}
+
+fn load(env: &Env, index: u32) -> Result<Observation, PairError> {
+    get_observation(env, index).ok_or(PairError::NotInitialized)
+}
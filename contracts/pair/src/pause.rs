@@ -0,0 +1,24 @@
+use soroban_sdk::Env;
+
+use crate::{
+    errors::PairError,
+    storage::{get_pair_paused, PairStorage},
+    FactoryClient,
+};
+
+/// Whether the pair is paused, either on its own or through a global
+/// factory pause.
+pub fn is_paused(env: &Env, state: &PairStorage) -> bool {
+    get_pair_paused(env) || FactoryClient::new(env, &state.factory).is_paused()
+}
+
+/// Reverts with `Paused` while the pair is paused.
+///
+/// Guards `mint`, `swap` and `flash_loan`. `burn` is deliberately left
+/// unguarded so LPs can always withdraw during an incident.
+pub fn require_not_paused(env: &Env, state: &PairStorage) -> Result<(), PairError> {
+    if is_paused(env, state) {
+        return Err(PairError::Paused);
+    }
+    Ok(())
+}
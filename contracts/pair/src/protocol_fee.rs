@@ -0,0 +1,75 @@
+use soroban_sdk::{Env, U256};
+
+use crate::{errors::PairError, math, storage::PairStorage, FactoryClient, LpTokenClient};
+
+/// Basis point denominator for the protocol's share of fee growth.
+const SHARE_DENOMINATOR: i128 = 10_000;
+
+/// Mints the protocol's cut of accrued swap fees to the factory's `fee_to`.
+///
+/// Follows Uniswap V2's `_mintFee`: swap fees grow `sqrt(reserve_a * reserve_b)`
+/// between liquidity events, and the protocol is minted LP shares worth
+/// `share_bps` of that growth, diluting existing LPs by exactly that amount.
+///
+/// # Math
+///
+/// ```text
+/// root_k      = sqrt(reserve_a * reserve_b)
+/// root_k_last = sqrt(k_last)
+/// φ           = share_bps / 10_000
+/// liquidity   = total_supply * (root_k - root_k_last) * φ
+///             / (root_k * (1 - φ) + root_k_last * φ)
+/// ```
+///
+/// With `φ = 1/6` this reduces to Uniswap's
+/// `total_supply * (root_k - root_k_last) / (5 * root_k + root_k_last)`.
+///
+/// Must be called with the reserves as they were *before* the liquidity event.
+/// When the protocol fee is off, any stale `k_last` is cleared so that turning
+/// it back on later does not retroactively charge for past growth.
+///
+/// # Returns
+/// `true` when the protocol fee is on; callers must then snapshot `k_last`
+/// from the post-event reserves.
+pub fn mint_fee(env: &Env, state: &mut PairStorage) -> Result<bool, PairError> {
+    let factory = FactoryClient::new(env, &state.factory);
+
+    let fee_to = match factory.fee_to() {
+        Some(fee_to) => fee_to,
+        None => {
+            state.k_last = U256::from_u32(env, 0);
+            return Ok(false);
+        }
+    };
+
+    if state.k_last == U256::from_u32(env, 0) {
+        return Ok(true);
+    }
+
+    let k = math::mul_wide(env, state.reserve_a, state.reserve_b).ok_or(PairError::Overflow)?;
+    let root_k = math::sqrt_wide(env, &k);
+    let root_k_last = math::sqrt_wide(env, &state.k_last);
+
+    if root_k <= root_k_last {
+        return Ok(true);
+    }
+
+    let share = factory.protocol_fee_share_bps() as i128;
+    let lp_client = LpTokenClient::new(env, &state.lp_token);
+    let total_supply = lp_client.total_supply();
+
+    // Roots approach 2^127 for large pools, so the whole expression is
+    // evaluated at 256 bits.
+    let wide = |value: i128| math::to_u256(env, value).ok_or(PairError::Overflow);
+    let numerator = wide(total_supply)?.mul(&wide(root_k - root_k_last)?).mul(&wide(share)?);
+    let denominator = wide(root_k)?
+        .mul(&wide(SHARE_DENOMINATOR - share)?)
+        .add(&wide(root_k_last)?.mul(&wide(share)?));
+
+    let liquidity = math::from_u256(&numerator.div(&denominator)).ok_or(PairError::Overflow)?;
+    if liquidity > 0 {
+        lp_client.mint(&fee_to, &liquidity);
+    }
+
+    Ok(true)
+}
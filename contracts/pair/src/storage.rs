@@ -1,8 +1,38 @@
-use soroban_sdk::{contracttype, Address, Env};
+use soroban_sdk::{contracttype, Address, Env, U256};
+
+use crate::errors::PairError;
+
+/// Layout version of the data this code reads and writes. Bump it, and add a
+/// step to [`migrate`], whenever a stored type changes shape.
+pub const STORAGE_VERSION: u32 = 1;
+
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
 
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct PairStorage {
+    pub factory: Address,
+    pub token_a: Address,
+    pub token_b: Address,
+    pub lp_token: Address,
+    pub reserve_a: i128,
+    pub reserve_b: i128,
+    pub block_timestamp_last: u64,
+    pub price_a_cumulative: i128,
+    pub price_b_cumulative: i128,
+    /// `reserve_a * reserve_b` as of the last liquidity event, while the
+    /// protocol fee is on. Kept at 256 bits since it overflows `i128`.
+    pub k_last: U256,
+}
+
+/// [`PairStorage`] as written before storage versioning (version 0), when
+/// `k_last` was an `i128`. Frozen: only read by the 0 → 1 migration.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PairStorageV0 {
     pub factory: Address,
     pub token_a: Address,
     pub token_b: Address,
@@ -29,6 +59,45 @@ pub struct FeeState {
     pub decay_threshold_blocks: u64,
 }
 
+/// Governance-tunable parameters of the dynamic fee engine.
+///
+/// Supplied by the factory at `initialize` and updated through
+/// `set_fee_params`; the runtime fields of [`FeeState`] are left untouched.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeParams {
+    pub ema_alpha: i128,
+    pub baseline_fee_bps: u32,
+    pub min_fee_bps: u32,
+    pub max_fee_bps: u32,
+    pub ramp_up_multiplier: u32,
+    pub cooldown_divisor: u32,
+    pub decay_threshold_blocks: u64,
+}
+
+/// A point-in-time snapshot of the cumulative price accumulators.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub price_a_cumulative: i128,
+    pub price_b_cumulative: i128,
+    /// `false` for slots pre-allocated by `grow` but not yet written.
+    pub initialized: bool,
+}
+
+/// Cursor and capacity of the observation ring buffer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObservationState {
+    /// Slot holding the most recent observation.
+    pub index: u32,
+    /// Number of slots currently in rotation.
+    pub cardinality: u32,
+    /// Capacity the buffer expands to once `index` wraps back to the start.
+    pub cardinality_next: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ReentrancyGuard {
@@ -44,18 +113,31 @@ pub enum DataKey {
     FeeState,
     /// Reentrancy lock for flash loan guard.
     Guard,
+    /// Per-pair pause flag, toggled by the factory.
+    Paused,
+    /// Layout version of the stored data; see [`STORAGE_VERSION`].
+    StorageVersion,
+    /// Observation ring buffer cursor and capacity.
+    ObservationState,
+    /// Observation ring buffer slot, kept in persistent storage.
+    Observation(u32),
 }
 
 // ---------------------------------------------------------------------------
 // PairStorage helpers
 // ---------------------------------------------------------------------------
 
+pub fn has_pair_state(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::PairState)
+}
+
 pub fn get_pair_state(env: &Env) -> Option<PairStorage> {
     env.storage().instance().get(&DataKey::PairState)
 }
 
 pub fn set_pair_state(env: &Env, state: &PairStorage) {
     env.storage().instance().set(&DataKey::PairState, state);
+    extend_instance_ttl(env);
 }
 
 // ---------------------------------------------------------------------------
@@ -68,6 +150,7 @@ pub fn get_fee_state(env: &Env) -> Option<FeeState> {
 
 pub fn set_fee_state(env: &Env, state: &FeeState) {
     env.storage().instance().set(&DataKey::FeeState, state);
+    extend_instance_ttl(env);
 }
 
 // ---------------------------------------------------------------------------
@@ -81,3 +164,120 @@ pub fn get_reentrancy_guard(env: &Env) -> ReentrancyGuard {
 pub fn set_reentrancy_guard(env: &Env, guard: &ReentrancyGuard) {
     env.storage().instance().set(&DataKey::Guard, guard);
 }
+
+pub fn get_pair_paused(env: &Env) -> bool {
+    env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+}
+
+pub fn set_pair_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+    extend_instance_ttl(env);
+}
+
+/// Stored layout version. Pairs initialized before versioning was introduced
+/// have no entry and are on version 0.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage().instance().get(&DataKey::StorageVersion).unwrap_or(0)
+}
+
+pub fn set_storage_version(env: &Env, version: u32) {
+    env.storage().instance().set(&DataKey::StorageVersion, &version);
+    extend_instance_ttl(env);
+}
+
+/// Brings stored data up to [`STORAGE_VERSION`], one version at a time.
+///
+/// Idempotent. Each step rewrites entries from version `n` to `n + 1`,
+/// typically by reading a frozen copy of the old type and writing the new
+/// one. Refuses data written by newer code, since it cannot be read safely.
+///
+/// Returns the version migrated to.
+pub fn migrate(env: &Env) -> Result<u32, PairError> {
+    let version = get_storage_version(env);
+    if version > STORAGE_VERSION {
+        return Err(PairError::UnsupportedStorageVersion);
+    }
+    if version < 1 {
+        migrate_v0_to_v1(env)?;
+    }
+    set_storage_version(env, STORAGE_VERSION);
+    Ok(STORAGE_VERSION)
+}
+
+/// 0 → 1: widens `k_last` to 256 bits and seeds the observation buffer,
+/// which version 0 pairs don't have, with the pair's current accumulators.
+fn migrate_v0_to_v1(env: &Env) -> Result<(), PairError> {
+    let legacy: PairStorageV0 =
+        env.storage().instance().get(&DataKey::PairState).ok_or(PairError::NotInitialized)?;
+    let state = PairStorage {
+        factory: legacy.factory,
+        token_a: legacy.token_a,
+        token_b: legacy.token_b,
+        lp_token: legacy.lp_token,
+        reserve_a: legacy.reserve_a,
+        reserve_b: legacy.reserve_b,
+        block_timestamp_last: legacy.block_timestamp_last,
+        price_a_cumulative: legacy.price_a_cumulative,
+        price_b_cumulative: legacy.price_b_cumulative,
+        k_last: U256::from_u128(env, legacy.k_last.max(0) as u128),
+    };
+    set_pair_state(env, &state);
+    if get_observation_state(env).is_none() {
+        crate::oracle::observations::initialize(env, &state);
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Observation helpers
+// ---------------------------------------------------------------------------
+
+pub fn get_observation_state(env: &Env) -> Option<ObservationState> {
+    env.storage().instance().get(&DataKey::ObservationState)
+}
+
+pub fn set_observation_state(env: &Env, state: &ObservationState) {
+    env.storage().instance().set(&DataKey::ObservationState, state);
+    extend_instance_ttl(env);
+}
+
+pub fn get_observation(env: &Env, index: u32) -> Option<Observation> {
+    let key = DataKey::Observation(index);
+    let observation = env.storage().persistent().get(&key);
+    if observation.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    observation
+}
+
+pub fn set_observation(env: &Env, index: u32, observation: &Observation) {
+    let key = DataKey::Observation(index);
+    env.storage().persistent().set(&key, observation);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Extends the TTL of observation slot `index`, if it has been allocated.
+pub fn extend_observation_ttl(env: &Env, index: u32) {
+    let key = DataKey::Observation(index);
+    if env.storage().persistent().has(&key) {
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// TTL helpers
+// ---------------------------------------------------------------------------
+
+/// Extend instance storage TTL to keep the pair alive. Called on every write,
+/// so any state-changing call keeps the pair's instance entry live.
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn extend_persistent_ttl(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
+}
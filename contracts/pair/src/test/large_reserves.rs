@@ -0,0 +1,172 @@
+//! Pools whose reserve products overflow `i128`.
+//!
+//! An 18-decimal token with a supply in the trillions has reserves around
+//! 1e30, so `reserve_a * reserve_b` is ~1e60 — far beyond `i128::MAX` (~1.7e38).
+
+use super::PairSetup;
+use crate::errors::PairError;
+use crate::math::{
+    fee_adjusted, from_u256, mul_div, mul_wide, product_covers, sqrt_wide, MINIMUM_LIQUIDITY,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, U256};
+
+const E18: i128 = 1_000_000_000_000_000_000;
+
+// ---------------------------------------------------------------------------
+// 256-bit helpers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_mul_div_survives_i128_overflowing_intermediate() {
+    let env = Env::default();
+    let a = 3 * E18 * E18;
+    assert!(a.checked_mul(a).is_none());
+
+    assert_eq!(mul_div(&env, a, a, a), Some(a));
+    assert_eq!(mul_div(&env, a, 7 * E18, 7 * E18), Some(a));
+}
+
+#[test]
+fn test_mul_div_rounds_down() {
+    let env = Env::default();
+
+    assert_eq!(mul_div(&env, 10, 10, 3), Some(33));
+    assert_eq!(mul_div(&env, 10, 9, 3), Some(30));
+    assert_eq!(mul_div(&env, 0, 9, 3), Some(0));
+}
+
+#[test]
+fn test_mul_div_rejects_invalid_inputs() {
+    let env = Env::default();
+
+    assert_eq!(mul_div(&env, 1, 1, 0), None);
+    assert_eq!(mul_div(&env, -1, 1, 1), None);
+    assert_eq!(mul_div(&env, i128::MAX, i128::MAX, 1), None);
+}
+
+#[test]
+fn test_sqrt_wide_floors_root_of_large_products() {
+    let env = Env::default();
+    let root = 5 * E18 * E18;
+
+    assert_eq!(sqrt_wide(&env, &mul_wide(&env, root, root).unwrap()), root);
+    let below = mul_wide(&env, root, root).unwrap().sub(&U256::from_u32(&env, 1));
+    assert_eq!(sqrt_wide(&env, &below), root - 1);
+    assert_eq!(sqrt_wide(&env, &U256::from_u32(&env, 0)), 0);
+    assert_eq!(sqrt_wide(&env, &U256::from_u32(&env, 15)), 3);
+}
+
+#[test]
+fn test_fee_adjusted_balances_do_not_overflow_near_i128_max() {
+    let env = Env::default();
+    assert!(i128::MAX.checked_mul(10_000).is_none());
+
+    let adjusted = fee_adjusted(&env, i128::MAX, i128::MAX / 2, 30).unwrap();
+    let expected = U256::from_u128(&env, i128::MAX as u128)
+        .mul(&U256::from_u32(&env, 10_000))
+        .sub(&U256::from_u128(&env, (i128::MAX / 2) as u128).mul(&U256::from_u32(&env, 30)));
+    assert_eq!(adjusted, expected);
+
+    // A fee that eats the whole balance leaves nothing to price against.
+    assert_eq!(fee_adjusted(&env, 1, 1, 10_000), None);
+    assert_eq!(fee_adjusted(&env, -1, 0, 30), None);
+}
+
+#[test]
+fn test_product_covers_is_exact_beyond_256_bits() {
+    let env = Env::default();
+    let max = i128::MAX;
+    let scaled_max = U256::from_u128(&env, max as u128).mul(&U256::from_u32(&env, 10_000));
+
+    // (max * 1e4)^2 and max^2 * 1e8 are equal and both exceed 256 bits.
+    assert_eq!(product_covers(&env, &scaled_max, &scaled_max, max, max, 100_000_000), Some(true));
+    let below = scaled_max.sub(&U256::from_u32(&env, 1));
+    assert_eq!(product_covers(&env, &below, &scaled_max, max, max, 100_000_000), Some(false));
+
+    assert_eq!(
+        product_covers(&env, &U256::from_u32(&env, 7), &U256::from_u32(&env, 3), 5, 4, 1),
+        Some(true)
+    );
+    assert_eq!(
+        product_covers(&env, &U256::from_u32(&env, 7), &U256::from_u32(&env, 3), 11, 2, 1),
+        Some(false)
+    );
+    assert_eq!(
+        product_covers(&env, &U256::from_u32(&env, 7), &U256::from_u32(&env, 0), 0, 9, 1),
+        Some(true)
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Pair flows
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_mint_swap_burn_with_large_reserves() {
+    let setup = PairSetup::new();
+    let (amount_a, amount_b) = (2_000_000_000_000 * E18, 8_000_000_000_000 * E18);
+
+    let liquidity = setup.add_liquidity(amount_a, amount_b);
+    assert_eq!(liquidity, 4_000_000_000_000 * E18 - MINIMUM_LIQUIDITY);
+
+    let amount_out = setup.swap_a_for_b(1_000_000 * E18);
+    assert!(amount_out > 0);
+
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    let k_before = mul_wide(&setup.env, amount_a, amount_b).unwrap();
+    assert!(mul_wide(&setup.env, reserve_a, reserve_b).unwrap() >= k_before);
+
+    // The pair's own balance (holding the locked minimum) is burned alongside.
+    let burned = liquidity / 2 + MINIMUM_LIQUIDITY;
+    let supply = liquidity + MINIMUM_LIQUIDITY;
+    let (out_a, out_b) = setup.remove_liquidity(liquidity / 2);
+    assert_eq!(out_a, mul_div(&setup.env, burned, reserve_a, supply).unwrap());
+    assert_eq!(out_b, mul_div(&setup.env, burned, reserve_b, supply).unwrap());
+}
+
+#[test]
+fn test_protocol_fee_on_large_pool_snapshots_wide_k_last() {
+    let setup = PairSetup::new();
+    let fee_to = Address::generate(&setup.env);
+    setup.factory.set_fee_to(&Some(fee_to.clone()));
+    let (amount_a, amount_b) = (1_000_000_000_000 * E18, 1_000_000_000_000 * E18);
+
+    setup.add_liquidity(amount_a, amount_b);
+
+    let k_last = setup.state().k_last;
+    assert_eq!(k_last, mul_wide(&setup.env, amount_a, amount_b).unwrap());
+    assert_eq!(from_u256(&k_last), None);
+
+    setup.swap_a_for_b(1_000_000_000 * E18);
+    setup.add_liquidity(E18, E18);
+    assert!(setup.lp.balance(&fee_to) > 0);
+}
+
+#[test]
+fn test_swap_near_i128_limits_enforces_k_exactly() {
+    let setup = PairSetup::new();
+    let env = &setup.env;
+    // Balances above i128::MAX / 10_000, where `balance * 10_000` overflows.
+    let reserve = i128::MAX / 4;
+    setup.add_liquidity(reserve, reserve);
+
+    let amount_in = i128::MAX / 10_000_000;
+    let fee = setup.pair.get_current_fee_bps();
+    let in_with_fee =
+        U256::from_u128(env, amount_in as u128).mul(&U256::from_u32(env, 10_000 - fee));
+    let amount_out = from_u256(&in_with_fee.mul(&U256::from_u128(env, reserve as u128)).div(
+        &U256::from_u128(env, reserve as u128).mul(&U256::from_u32(env, 10_000)).add(&in_with_fee),
+    ))
+    .unwrap();
+
+    setup.token_a.mint(&setup.pair.address, &amount_in);
+    assert_eq!(
+        setup.pair.try_swap(&0, &(amount_out + 1), &setup.user),
+        Err(Ok(PairError::InvalidK))
+    );
+    setup.pair.swap(&0, &amount_out, &setup.user);
+
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    assert_eq!(reserve_a, reserve + amount_in);
+    assert_eq!(reserve_b, reserve - amount_out);
+}
@@ -14,9 +14,221 @@
 //   dynamic_fee.rs   — unit tests for dynamic fee engine (volatility, decay)
 //   sync.rs          — tests for reserve synchronization (Pair::sync)
 //   reentrancy.rs    — tests for reentrancy guard (acquire/release)
+//   protocol_fee.rs  — protocol fee minting to `fee_to` on mint/burn
+//   pause.rs         — factory-wide and per-pair pause enforcement
+//   upgrade.rs       — factory-driven code upgrades and storage migration
+//   oracle.rs        — cumulative-price TWAP accrual and consultation
+//   large_reserves.rs — 256-bit reserve arithmetic on 18-decimal-scale pools
+//   ttl.rs           — instance/observation TTL extension and `bump`
 //
 // ---------------------------------------------------------------------------
 
 mod events;
+mod large_reserves;
+mod oracle;
+mod pause;
+mod protocol_fee;
 mod swap_math;
+mod ttl;
+mod upgrade;
 mod views;
+
+use crate::storage::FeeParams;
+use crate::{Pair, PairClient};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, testutils::Address as _, token::StellarAssetClient,
+    Address, Env,
+};
+
+// ---------------------------------------------------------------------------
+// Shared mocks
+// ---------------------------------------------------------------------------
+
+/// Minimal factory exposing the views pairs read at runtime.
+#[contract]
+pub struct MockFactory;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum MockFactoryKey {
+    FeeTo,
+    FeeShare,
+    Paused,
+}
+
+#[contractimpl]
+impl MockFactory {
+    pub fn set_fee_to(env: Env, fee_to: Option<Address>) {
+        env.storage().instance().set(&MockFactoryKey::FeeTo, &fee_to);
+    }
+
+    pub fn set_paused(env: Env, paused: bool) {
+        env.storage().instance().set(&MockFactoryKey::Paused, &paused);
+    }
+
+    pub fn set_fee_share(env: Env, share_bps: u32) {
+        env.storage().instance().set(&MockFactoryKey::FeeShare, &share_bps);
+    }
+
+    pub fn fee_to(env: Env) -> Option<Address> {
+        env.storage().instance().get(&MockFactoryKey::FeeTo).unwrap_or(None)
+    }
+
+    pub fn protocol_fee_share_bps(env: Env) -> u32 {
+        env.storage().instance().get(&MockFactoryKey::FeeShare).unwrap_or(1_667)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage().instance().get(&MockFactoryKey::Paused).unwrap_or(false)
+    }
+}
+
+/// Minimal LP token with unrestricted mint/burn and a tracked total supply.
+#[contract]
+pub struct MockLpToken;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum MockLpKey {
+    Balance(Address),
+    TotalSupply,
+    BumpCount,
+}
+
+#[contractimpl]
+impl MockLpToken {
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage().instance().get(&MockLpKey::Balance(id)).unwrap_or(0)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage().instance().get(&MockLpKey::TotalSupply).unwrap_or(0)
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage().instance().set(&MockLpKey::Balance(to), &(balance + amount));
+        let supply = Self::total_supply(env.clone());
+        env.storage().instance().set(&MockLpKey::TotalSupply, &(supply + amount));
+    }
+
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), from.clone());
+        env.storage().instance().set(&MockLpKey::Balance(from), &(balance - amount));
+        let supply = Self::total_supply(env.clone());
+        env.storage().instance().set(&MockLpKey::TotalSupply, &(supply - amount));
+    }
+
+    pub fn bump(env: Env) {
+        let count: u32 = env.storage().instance().get(&MockLpKey::BumpCount).unwrap_or(0);
+        env.storage().instance().set(&MockLpKey::BumpCount, &(count + 1));
+    }
+
+    pub fn bump_count(env: Env) -> u32 {
+        env.storage().instance().get(&MockLpKey::BumpCount).unwrap_or(0)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage().instance().set(&MockLpKey::Balance(from), &(from_balance - amount));
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage().instance().set(&MockLpKey::Balance(to), &(to_balance + amount));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Shared setup
+// ---------------------------------------------------------------------------
+
+/// A fully wired pair: sorted SAC tokens, mock LP token and mock factory.
+pub struct PairSetup<'a> {
+    pub env: Env,
+    pub pair: PairClient<'a>,
+    pub factory: MockFactoryClient<'a>,
+    pub lp: MockLpTokenClient<'a>,
+    pub token_a: StellarAssetClient<'a>,
+    pub token_b: StellarAssetClient<'a>,
+    pub user: Address,
+}
+
+impl<'a> PairSetup<'a> {
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let t0 = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let t1 = env.register_stellar_asset_contract_v2(admin).address();
+        let (token_a, token_b) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+        let factory = env.register_contract(None, MockFactory);
+        let lp = env.register_contract(None, MockLpToken);
+        let pair = env.register_contract(None, Pair);
+        let pair_client = PairClient::new(&env, &pair);
+        pair_client.initialize(&factory, &token_a, &token_b, &lp, &default_fee_params());
+
+        PairSetup {
+            pair: pair_client,
+            factory: MockFactoryClient::new(&env, &factory),
+            lp: MockLpTokenClient::new(&env, &lp),
+            token_a: StellarAssetClient::new(&env, &token_a),
+            token_b: StellarAssetClient::new(&env, &token_b),
+            user: Address::generate(&env),
+            env,
+        }
+    }
+
+    /// Deposits `amount_a` / `amount_b` into the pair and mints LP to `user`.
+    pub fn add_liquidity(&self, amount_a: i128, amount_b: i128) -> i128 {
+        self.token_a.mint(&self.pair.address, &amount_a);
+        self.token_b.mint(&self.pair.address, &amount_b);
+        self.pair.mint(&self.user)
+    }
+
+    /// Sends `liquidity` of the user's LP back to the pair and burns it.
+    pub fn remove_liquidity(&self, liquidity: i128) -> (i128, i128) {
+        self.lp.transfer(&self.user, &self.pair.address, &liquidity);
+        self.pair.burn(&self.user)
+    }
+
+    /// Swaps an exact `amount_in` of token_a for token_b at the current fee.
+    pub fn swap_a_for_b(&self, amount_in: i128) -> i128 {
+        let (reserve_a, reserve_b, _) = self.pair.get_reserves();
+        let fee = self.pair.get_current_fee_bps() as i128;
+        let in_with_fee = amount_in * (10_000 - fee);
+        let amount_out = crate::math::mul_div(
+            &self.env,
+            in_with_fee,
+            reserve_b,
+            reserve_a * 10_000 + in_with_fee,
+        )
+        .unwrap();
+        self.token_a.mint(&self.pair.address, &amount_in);
+        self.pair.swap(&0, &amount_out, &self.user);
+        amount_out
+    }
+
+    /// Reads the raw pair storage.
+    pub fn state(&self) -> crate::storage::PairStorage {
+        self.env
+            .as_contract(&self.pair.address, || crate::storage::get_pair_state(&self.env).unwrap())
+    }
+
+    /// Reads the stored `k_last`, narrowed to `i128` for small test pools.
+    pub fn k_last(&self) -> i128 {
+        crate::math::from_u256(&self.state().k_last).unwrap()
+    }
+}
+
+/// Fee parameters used by [`PairSetup`] pairs.
+pub fn default_fee_params() -> FeeParams {
+    FeeParams {
+        ema_alpha: crate::math::SCALE / 20,
+        baseline_fee_bps: 30,
+        min_fee_bps: 5,
+        max_fee_bps: 100,
+        ramp_up_multiplier: 2,
+        cooldown_divisor: 2,
+        decay_threshold_blocks: 100,
+    }
+}
@@ -0,0 +1,296 @@
+//! Tests for the cumulative-price TWAP oracle.
+
+use super::PairSetup;
+use crate::errors::PairError;
+use crate::math::SCALE;
+use crate::oracle::observations::MAX_OBSERVATION_BATCH;
+use crate::oracle::{consult_twap, update_cumulative_prices};
+use crate::storage::{DataKey, ObservationState};
+use soroban_sdk::{testutils::Ledger, vec, Env};
+
+const E30: i128 = 10i128.pow(30);
+
+// ---------------------------------------------------------------------------
+// Pure accumulator math
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_update_cumulative_prices_keeps_fractional_precision() {
+    let env = Env::default();
+    let (mut price_a, mut price_b) = (0i128, 0i128);
+
+    update_cumulative_prices(&env, 3_000, 1_000, 10, &mut price_a, &mut price_b);
+
+    // Plain integer division would round 1_000 / 3_000 down to zero.
+    assert_eq!(price_a, SCALE / 3 * 10);
+    assert_eq!(price_b, 3 * SCALE * 10);
+}
+
+#[test]
+fn test_update_cumulative_prices_skips_empty_pool_and_zero_time() {
+    let env = Env::default();
+    let (mut price_a, mut price_b) = (7i128, 9i128);
+
+    update_cumulative_prices(&env, 0, 1_000, 10, &mut price_a, &mut price_b);
+    update_cumulative_prices(&env, 1_000, 1_000, 0, &mut price_a, &mut price_b);
+
+    assert_eq!((price_a, price_b), (7, 9));
+}
+
+#[test]
+fn test_update_cumulative_prices_saturates_extreme_prices() {
+    let env = Env::default();
+    let (mut price_a, mut price_b) = (0i128, 0i128);
+
+    // reserve_b / reserve_a * SCALE is 1e44, past i128::MAX (~1.7e38).
+    update_cumulative_prices(&env, 1, E30, 1, &mut price_a, &mut price_b);
+
+    assert_eq!(price_a, i128::MAX);
+    assert_eq!(price_b, 0);
+}
+
+#[test]
+fn test_consult_twap_averages_over_window() {
+    assert_eq!(consult_twap(1_000 * SCALE, 1_600 * SCALE, 300), Ok(2 * SCALE));
+}
+
+#[test]
+fn test_consult_twap_handles_wrapped_accumulator() {
+    let start = i128::MAX - SCALE;
+    let end = start.wrapping_add(4 * SCALE * 100);
+    assert!(end < start, "accumulator should have wrapped");
+
+    assert_eq!(consult_twap(start, end, 100), Ok(4 * SCALE));
+}
+
+#[test]
+fn test_consult_twap_rejects_zero_window() {
+    assert_eq!(consult_twap(0, 100, 0), Err(PairError::InvalidInput));
+}
+
+// ---------------------------------------------------------------------------
+// Accrual on every reserve-changing path
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_swap_accrues_cumulative_prices() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    setup.env.ledger().set_timestamp(100);
+    setup.swap_a_for_b(1_000);
+
+    let state = setup.state();
+    assert_eq!(state.price_a_cumulative, 4 * SCALE * 100);
+    assert_eq!(state.price_b_cumulative, SCALE / 4 * 100);
+    assert_eq!(state.block_timestamp_last, 100);
+}
+
+#[test]
+fn test_mint_and_burn_accrue_cumulative_prices() {
+    let setup = PairSetup::new();
+    let liquidity = setup.add_liquidity(1_000_000, 2_000_000);
+
+    setup.env.ledger().set_timestamp(10);
+    setup.add_liquidity(1_000, 2_000);
+    assert_eq!(setup.state().price_a_cumulative, 2 * SCALE * 10);
+
+    setup.env.ledger().set_timestamp(30);
+    setup.remove_liquidity(liquidity / 2);
+    let state = setup.state();
+    assert_eq!(state.price_a_cumulative, 2 * SCALE * 30);
+    assert_eq!(state.block_timestamp_last, 30);
+}
+
+#[test]
+fn test_extreme_reserve_ratio_does_not_block_pool() {
+    let setup = PairSetup::new();
+    let liquidity = setup.add_liquidity(10_000, E30);
+
+    setup.env.ledger().set_timestamp(10);
+    setup.pair.sync();
+    setup.add_liquidity(10_000, E30);
+    setup.env.ledger().set_timestamp(20);
+    setup.remove_liquidity(liquidity);
+
+    let state = setup.state();
+    assert_eq!(state.block_timestamp_last, 20);
+    assert_eq!(state.price_a_cumulative, i128::MAX.wrapping_mul(20));
+}
+
+#[test]
+fn test_sync_accrues_with_fixed_point_precision() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(3_000_000, 1_000_000);
+
+    setup.env.ledger().set_timestamp(60);
+    setup.pair.sync();
+
+    let state = setup.state();
+    assert_eq!(state.price_a_cumulative, SCALE / 3 * 60);
+    assert_eq!(state.price_b_cumulative, 3 * SCALE * 60);
+}
+
+#[test]
+fn test_get_cumulative_prices_accrues_to_now_without_writing() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 2_000_000);
+
+    setup.env.ledger().set_timestamp(50);
+    let (price_a, price_b, timestamp) = setup.pair.get_cumulative_prices();
+
+    assert_eq!(price_a, 2 * SCALE * 50);
+    assert_eq!(price_b, SCALE / 2 * 50);
+    assert_eq!(timestamp, 50);
+    assert_eq!(setup.state().price_a_cumulative, 0);
+}
+
+#[test]
+fn test_twap_from_view_matches_constant_spot_price() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 5_000_000);
+
+    setup.env.ledger().set_timestamp(1_000);
+    let (start, _, t0) = setup.pair.get_cumulative_prices();
+    setup.env.ledger().set_timestamp(2_800);
+    let (end, _, t1) = setup.pair.get_cumulative_prices();
+
+    assert_eq!(consult_twap(start, end, t1 - t0), Ok(5 * SCALE));
+}
+
+// ---------------------------------------------------------------------------
+// Observation ring buffer
+// ---------------------------------------------------------------------------
+
+#[test]
+fn test_initialize_seeds_single_observation() {
+    let setup = PairSetup::new();
+    assert_eq!(
+        setup.pair.observation_state(),
+        ObservationState { index: 0, cardinality: 1, cardinality_next: 1 }
+    );
+}
+
+#[test]
+fn test_observations_written_once_per_timestamp() {
+    let setup = PairSetup::new();
+    setup.pair.grow_observations(&4);
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    setup.env.ledger().set_timestamp(100);
+    setup.swap_a_for_b(1_000);
+    setup.swap_a_for_b(1_000);
+
+    assert_eq!(setup.pair.observation_state().index, 1);
+}
+
+#[test]
+fn test_observe_interpolates_between_observations() {
+    let setup = PairSetup::new();
+    setup.pair.grow_observations(&4);
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    setup.env.ledger().set_timestamp(100);
+    setup.swap_a_for_b(10_000);
+
+    setup.env.ledger().set_timestamp(400);
+    let points = setup.pair.observe(&vec![&setup.env, 350, 300, 0]);
+    let (now_a, now_b, _) = setup.pair.get_cumulative_prices();
+
+    assert_eq!(points.get_unchecked(0), (4 * SCALE * 50, SCALE / 4 * 50));
+    assert_eq!(points.get_unchecked(1), (4 * SCALE * 100, SCALE / 4 * 100));
+    assert_eq!(points.get_unchecked(2), (now_a, now_b));
+}
+
+#[test]
+fn test_observe_rejects_targets_older_than_buffer() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    // With a single slot, each write overwrites the only observation.
+    setup.env.ledger().set_timestamp(100);
+    setup.swap_a_for_b(1_000);
+
+    setup.env.ledger().set_timestamp(200);
+    assert_eq!(
+        setup.pair.try_observe(&vec![&setup.env, 150]),
+        Err(Ok(PairError::ObservationTooOld))
+    );
+    assert_eq!(
+        setup.pair.try_observe(&vec![&setup.env, 201]),
+        Err(Ok(PairError::ObservationTooOld))
+    );
+    assert!(setup.pair.try_observe(&vec![&setup.env, 100]).is_ok());
+}
+
+#[test]
+fn test_ring_buffer_wraps_and_drops_oldest() {
+    let setup = PairSetup::new();
+    setup.pair.grow_observations(&2);
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    for timestamp in [100, 200, 300] {
+        setup.env.ledger().set_timestamp(timestamp);
+        setup.pair.sync();
+    }
+
+    assert_eq!(
+        setup.pair.observation_state(),
+        ObservationState { index: 1, cardinality: 2, cardinality_next: 2 }
+    );
+    setup.env.ledger().set_timestamp(300);
+    assert_eq!(
+        setup.pair.try_observe(&vec![&setup.env, 150]),
+        Err(Ok(PairError::ObservationTooOld))
+    );
+    assert_eq!(
+        setup.pair.observe(&vec![&setup.env, 50]).get_unchecked(0),
+        (4 * SCALE * 250, SCALE / 4 * 250)
+    );
+}
+
+#[test]
+fn test_grow_observations_bounds() {
+    let setup = PairSetup::new();
+
+    assert_eq!(setup.pair.grow_observations(&8), 8);
+    assert_eq!(setup.pair.grow_observations(&4), 8);
+    assert_eq!(setup.pair.try_grow_observations(&1_025), Err(Ok(PairError::InvalidInput)));
+    assert_eq!(setup.pair.observation_state().cardinality, 1);
+}
+
+#[test]
+fn test_grow_observations_is_incremental() {
+    let setup = PairSetup::new();
+
+    assert_eq!(setup.pair.grow_observations(&1_024), 1 + MAX_OBSERVATION_BATCH);
+    assert_eq!(setup.pair.grow_observations(&1_024), 1 + 2 * MAX_OBSERVATION_BATCH);
+    assert_eq!(setup.pair.grow_observations(&70), 70);
+    assert_eq!(setup.pair.observation_state().cardinality_next, 70);
+    assert!(!setup.env.as_contract(&setup.pair.address, || {
+        setup.env.storage().persistent().has(&DataKey::Observation(70))
+    }));
+}
+
+#[test]
+fn test_consult_weights_prices_by_time() {
+    let setup = PairSetup::new();
+    setup.pair.grow_observations(&4);
+    setup.add_liquidity(1_000_000, 4_000_000);
+
+    setup.env.ledger().set_timestamp(900);
+    setup.swap_a_for_b(100_000);
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    let price_after = reserve_b * SCALE / reserve_a;
+
+    setup.env.ledger().set_timestamp(1_800);
+    let (twap_a, _) = setup.pair.consult(&1_800);
+
+    assert_eq!(twap_a, (4 * SCALE * 900 + price_after * 900) / 1_800);
+}
+
+#[test]
+fn test_consult_rejects_zero_window() {
+    let setup = PairSetup::new();
+    assert_eq!(setup.pair.try_consult(&0), Err(Ok(PairError::InvalidInput)));
+}
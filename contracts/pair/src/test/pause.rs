@@ -0,0 +1,70 @@
+//! Tests for factory-wide and per-pair pause enforcement.
+
+use super::PairSetup;
+use crate::errors::PairError;
+use soroban_sdk::{testutils::Address as _, Address, Bytes};
+
+#[test]
+fn test_unpaused_by_default() {
+    let setup = PairSetup::new();
+    assert!(!setup.pair.is_paused());
+}
+
+#[test]
+fn test_factory_pause_blocks_mint_swap_and_flash_loan() {
+    let setup = PairSetup::new();
+    let liquidity = setup.add_liquidity(1_000_000, 1_000_000);
+    setup.factory.set_paused(&true);
+
+    assert!(setup.pair.is_paused());
+
+    setup.token_a.mint(&setup.pair.address, &1_000);
+    setup.token_b.mint(&setup.pair.address, &1_000);
+    assert_eq!(setup.pair.try_mint(&setup.user), Err(Ok(PairError::Paused)));
+    assert_eq!(setup.pair.try_swap(&0, &100, &setup.user), Err(Ok(PairError::Paused)));
+
+    let receiver = Address::generate(&setup.env);
+    assert_eq!(
+        setup.pair.try_flash_loan(&receiver, &1_000, &0, &Bytes::new(&setup.env)),
+        Err(Ok(PairError::Paused))
+    );
+
+    // LPs can still exit.
+    setup.lp.transfer(&setup.user, &setup.pair.address, &(liquidity / 2));
+    assert!(setup.pair.try_burn(&setup.user).is_ok());
+}
+
+#[test]
+fn test_factory_unpause_restores_trading() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 1_000_000);
+
+    setup.factory.set_paused(&true);
+    setup.factory.set_paused(&false);
+
+    assert!(!setup.pair.is_paused());
+    assert!(setup.swap_a_for_b(1_000) > 0);
+}
+
+#[test]
+fn test_pair_pause_is_independent_of_factory() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 1_000_000);
+
+    setup.pair.set_paused(&true);
+
+    assert!(setup.pair.is_paused());
+    assert!(!setup.factory.is_paused());
+    assert_eq!(setup.pair.try_swap(&0, &100, &setup.user), Err(Ok(PairError::Paused)));
+
+    setup.pair.set_paused(&false);
+    assert!(setup.swap_a_for_b(1_000) > 0);
+}
+
+#[test]
+fn test_set_paused_requires_factory_auth() {
+    let setup = PairSetup::new();
+    setup.pair.set_paused(&true);
+
+    assert_eq!(setup.env.auths()[0].0, setup.factory.address);
+}
@@ -0,0 +1,117 @@
+//! Tests for protocol fee minting to the factory's `fee_to` on mint/burn.
+
+use super::PairSetup;
+use crate::math::sqrt;
+use soroban_sdk::{testutils::Address as _, Address};
+
+/// Mirrors `protocol_fee::mint_fee` for expected-value assertions.
+fn expected_fee_liquidity(total_supply: i128, k: i128, k_last: i128, share_bps: i128) -> i128 {
+    let root_k = sqrt(k);
+    let root_k_last = sqrt(k_last);
+    total_supply * (root_k - root_k_last) * share_bps
+        / (root_k * (10_000 - share_bps) + root_k_last * share_bps)
+}
+
+#[test]
+fn test_fee_off_mints_nothing_and_keeps_k_last_zero() {
+    let setup = PairSetup::new();
+
+    setup.add_liquidity(1_000_000, 1_000_000);
+    setup.swap_a_for_b(50_000);
+    setup.add_liquidity(10_000, 10_000);
+
+    assert_eq!(setup.k_last(), 0);
+}
+
+#[test]
+fn test_fee_on_snapshots_k_last_on_mint() {
+    let setup = PairSetup::new();
+    let fee_to = Address::generate(&setup.env);
+    setup.factory.set_fee_to(&Some(fee_to.clone()));
+
+    setup.add_liquidity(1_000_000, 1_000_000);
+
+    assert_eq!(setup.k_last(), 1_000_000 * 1_000_000);
+    // No growth yet, so nothing is owed to the protocol.
+    assert_eq!(setup.lp.balance(&fee_to), 0);
+}
+
+#[test]
+fn test_fee_on_mints_share_of_growth_on_mint() {
+    let setup = PairSetup::new();
+    let fee_to = Address::generate(&setup.env);
+    setup.factory.set_fee_to(&Some(fee_to.clone()));
+
+    setup.add_liquidity(1_000_000, 1_000_000);
+    setup.swap_a_for_b(100_000);
+
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    let k_last = setup.k_last();
+    let supply = setup.lp.balance(&setup.user) + 1_000; // + MINIMUM_LIQUIDITY
+    let expected = expected_fee_liquidity(supply, reserve_a * reserve_b, k_last, 1_667);
+    assert!(expected > 0);
+
+    setup.add_liquidity(10_000, 10_000);
+
+    assert_eq!(setup.lp.balance(&fee_to), expected);
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    assert_eq!(setup.k_last(), reserve_a * reserve_b);
+}
+
+#[test]
+fn test_fee_on_mints_share_of_growth_on_burn() {
+    let setup = PairSetup::new();
+    let fee_to = Address::generate(&setup.env);
+    setup.factory.set_fee_to(&Some(fee_to.clone()));
+
+    let liquidity = setup.add_liquidity(1_000_000, 1_000_000);
+    setup.swap_a_for_b(100_000);
+
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    let k_last = setup.k_last();
+    let expected = expected_fee_liquidity(liquidity + 1_000, reserve_a * reserve_b, k_last, 1_667);
+
+    setup.remove_liquidity(liquidity / 2);
+
+    assert_eq!(setup.lp.balance(&fee_to), expected);
+    let (reserve_a, reserve_b, _) = setup.pair.get_reserves();
+    assert_eq!(setup.k_last(), reserve_a * reserve_b);
+}
+
+#[test]
+fn test_fee_share_is_configurable() {
+    let low = PairSetup::new();
+    let high = PairSetup::new();
+    for setup in [&low, &high] {
+        let fee_to = Address::generate(&setup.env);
+        setup.factory.set_fee_to(&Some(fee_to));
+    }
+    high.factory.set_fee_share(&5_000);
+
+    for setup in [&low, &high] {
+        setup.add_liquidity(1_000_000, 1_000_000);
+        setup.swap_a_for_b(100_000);
+        setup.add_liquidity(10_000, 10_000);
+    }
+
+    let low_fee = low.lp.total_supply() - low.lp.balance(&low.user) - 1_000;
+    let high_fee = high.lp.total_supply() - high.lp.balance(&high.user) - 1_000;
+    assert!(high_fee > low_fee, "50% share ({}) must exceed ~16.7% share ({})", high_fee, low_fee);
+}
+
+#[test]
+fn test_turning_fee_off_resets_k_last() {
+    let setup = PairSetup::new();
+    let fee_to = Address::generate(&setup.env);
+    setup.factory.set_fee_to(&Some(fee_to.clone()));
+
+    setup.add_liquidity(1_000_000, 1_000_000);
+    assert!(setup.k_last() > 0);
+
+    setup.factory.set_fee_to(&None);
+    setup.swap_a_for_b(100_000);
+    setup.add_liquidity(10_000, 10_000);
+
+    assert_eq!(setup.k_last(), 0);
+    assert_eq!(setup.lp.balance(&fee_to), 0);
+}
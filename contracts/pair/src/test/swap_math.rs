@@ -276,19 +276,19 @@ mod swap_math_tests {
     // ---- 12. mul_div: basic precision check ----
     #[test]
     fn test_mul_div_basic() {
-        let _env = Env::default();
+        let env = Env::default();
 
         // (SCALE * 2) * (SCALE * 3) / SCALE == SCALE * 6
-        let result = mul_div(SCALE * 2, SCALE * 3, SCALE);
+        let result = mul_div(&env, SCALE * 2, SCALE * 3, SCALE);
         assert_eq!(result, Some(SCALE * 6), "mul_div basic multiplication failed");
     }
 
     // ---- 13. mul_div: division by zero returns None ----
     #[test]
     fn test_mul_div_zero_denominator() {
-        let _env = Env::default();
+        let env = Env::default();
 
-        let result = mul_div(100, 200, 0);
+        let result = mul_div(&env, 100, 200, 0);
         assert_eq!(result, None, "mul_div with zero denominator must return None");
     }
 
@@ -0,0 +1,103 @@
+//! Tests for storage TTL extension and the permissionless `bump` entrypoint.
+
+use super::PairSetup;
+use crate::errors::PairError;
+use crate::oracle::observations::MAX_OBSERVATION_BATCH;
+use crate::storage::{DataKey, INSTANCE_BUMP_AMOUNT, PERSISTENT_BUMP_AMOUNT};
+use crate::{Pair, PairClient};
+use soroban_sdk::testutils::storage::{Instance, Persistent};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::Env;
+
+/// Advances the ledger, first keeping the pair's mock dependencies alive so
+/// only the pair's own TTL handling is under test.
+fn advance_ledgers(setup: &PairSetup, ledgers: u32) {
+    let dependencies =
+        [&setup.factory.address, &setup.lp.address, &setup.token_a.address, &setup.token_b.address];
+    for contract in dependencies {
+        setup.env.as_contract(contract, || {
+            setup.env.storage().instance().extend_ttl(ledgers, ledgers + 1_000);
+        });
+    }
+    setup.env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+fn instance_ttl(setup: &PairSetup) -> u32 {
+    setup.env.as_contract(&setup.pair.address, || setup.env.storage().instance().get_ttl())
+}
+
+fn observation_ttl(setup: &PairSetup, index: u32) -> u32 {
+    setup.env.as_contract(&setup.pair.address, || {
+        setup.env.storage().persistent().get_ttl(&DataKey::Observation(index))
+    })
+}
+
+#[test]
+fn test_state_changes_extend_instance_ttl() {
+    let setup = PairSetup::new();
+    assert_eq!(instance_ttl(&setup), INSTANCE_BUMP_AMOUNT);
+
+    advance_ledgers(&setup, INSTANCE_BUMP_AMOUNT - 10);
+    setup.add_liquidity(1_000_000, 1_000_000);
+
+    assert_eq!(instance_ttl(&setup), INSTANCE_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_observation_writes_extend_persistent_ttl() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 1_000_000);
+    assert_eq!(observation_ttl(&setup, 0), PERSISTENT_BUMP_AMOUNT);
+}
+
+#[test]
+fn test_bump_extends_instance_and_newest_observation() {
+    let setup = PairSetup::new();
+    setup.pair.grow_observations(&3);
+
+    advance_ledgers(&setup, PERSISTENT_BUMP_AMOUNT - 10);
+    assert_eq!(instance_ttl(&setup), 10);
+    assert_eq!(observation_ttl(&setup, 0), 10);
+
+    setup.pair.bump();
+
+    assert_eq!(instance_ttl(&setup), INSTANCE_BUMP_AMOUNT);
+    assert_eq!(observation_ttl(&setup, 0), PERSISTENT_BUMP_AMOUNT);
+    // Pre-allocated slots are left to `bump_observations`.
+    assert_eq!(observation_ttl(&setup, 2), 10);
+}
+
+#[test]
+fn test_bump_observations_pages_through_the_buffer() {
+    let setup = PairSetup::new();
+    setup.env.budget().reset_unlimited();
+    for target in [32, 64, 70] {
+        setup.pair.grow_observations(&target);
+    }
+    advance_ledgers(&setup, PERSISTENT_BUMP_AMOUNT - 10);
+
+    assert_eq!(setup.pair.bump_observations(&0, &u32::MAX), MAX_OBSERVATION_BATCH);
+    assert_eq!(observation_ttl(&setup, MAX_OBSERVATION_BATCH - 1), PERSISTENT_BUMP_AMOUNT);
+    assert_eq!(observation_ttl(&setup, MAX_OBSERVATION_BATCH), 10);
+
+    assert_eq!(setup.pair.bump_observations(&32, &32), 64);
+    assert_eq!(setup.pair.bump_observations(&64, &32), 70);
+    assert_eq!(setup.pair.bump_observations(&70, &32), 70);
+    for index in 0..70 {
+        assert_eq!(observation_ttl(&setup, index), PERSISTENT_BUMP_AMOUNT);
+    }
+}
+
+#[test]
+fn test_bump_extends_lp_token() {
+    let setup = PairSetup::new();
+    setup.pair.bump();
+    assert_eq!(setup.lp.bump_count(), 1);
+}
+
+#[test]
+fn test_bump_requires_initialized_pair() {
+    let env = Env::default();
+    let pair = PairClient::new(&env, &env.register_contract(None, Pair));
+    assert_eq!(pair.try_bump(), Err(Ok(PairError::NotInitialized)));
+}
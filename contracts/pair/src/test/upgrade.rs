@@ -0,0 +1,100 @@
+//! Tests for pair code upgrades and stored-data migration.
+
+use super::PairSetup;
+use crate::errors::PairError;
+use crate::storage::{self, DataKey, PairStorageV0, STORAGE_VERSION};
+use soroban_sdk::{testutils::Ledger, Bytes, U256};
+
+#[test]
+fn test_initialize_stamps_storage_version() {
+    let setup = PairSetup::new();
+    assert_eq!(setup.pair.storage_version(), STORAGE_VERSION);
+}
+
+#[test]
+fn test_migrate_is_idempotent() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 1_000_000);
+
+    assert_eq!(setup.pair.migrate(), STORAGE_VERSION);
+    assert_eq!(setup.pair.migrate(), STORAGE_VERSION);
+    assert_eq!(setup.state().reserve_a, 1_000_000);
+}
+
+#[test]
+fn test_migrate_upgrades_legacy_layout() {
+    let setup = PairSetup::new();
+    setup.add_liquidity(1_000_000, 4_000_000);
+    let env = &setup.env;
+    let current = setup.state();
+
+    // Rewrite the pair as a version 0 deployment left it: an i128 `k_last`,
+    // no observation buffer and no version stamp.
+    env.as_contract(&setup.pair.address, || {
+        let instance = env.storage().instance();
+        instance.set(
+            &DataKey::PairState,
+            &PairStorageV0 {
+                factory: current.factory.clone(),
+                token_a: current.token_a.clone(),
+                token_b: current.token_b.clone(),
+                lp_token: current.lp_token.clone(),
+                reserve_a: current.reserve_a,
+                reserve_b: current.reserve_b,
+                block_timestamp_last: 900,
+                price_a_cumulative: 123,
+                price_b_cumulative: 456,
+                k_last: 4_000_000_000_000,
+            },
+        );
+        instance.remove(&DataKey::ObservationState);
+        instance.remove(&DataKey::StorageVersion);
+        env.storage().persistent().remove(&DataKey::Observation(0));
+    });
+    assert_eq!(setup.pair.storage_version(), 0);
+
+    assert_eq!(setup.pair.migrate(), STORAGE_VERSION);
+
+    let migrated = setup.state();
+    assert_eq!(migrated.k_last, U256::from_u128(env, 4_000_000_000_000));
+    assert_eq!((migrated.reserve_a, migrated.reserve_b), (1_000_000, 4_000_000));
+    assert_eq!((migrated.price_a_cumulative, migrated.price_b_cumulative), (123, 456));
+    env.as_contract(&setup.pair.address, || {
+        let cursor = storage::get_observation_state(env).unwrap();
+        assert_eq!((cursor.index, cursor.cardinality, cursor.cardinality_next), (0, 1, 1));
+        let seed = storage::get_observation(env, 0).unwrap();
+        assert_eq!(
+            (seed.timestamp, seed.price_a_cumulative, seed.price_b_cumulative),
+            (900, 123, 456)
+        );
+    });
+    assert_eq!(setup.pair.storage_version(), STORAGE_VERSION);
+    assert_eq!(setup.pair.migrate(), STORAGE_VERSION);
+
+    // The migrated pair trades and records observations as usual.
+    env.ledger().set_timestamp(1_000);
+    setup.swap_a_for_b(10_000);
+    env.as_contract(&setup.pair.address, || {
+        assert_eq!(storage::get_observation(env, 0).unwrap().timestamp, 1_000);
+    });
+}
+
+#[test]
+fn test_migrate_rejects_data_from_newer_code() {
+    let setup = PairSetup::new();
+    setup.env.as_contract(&setup.pair.address, || {
+        storage::set_storage_version(&setup.env, STORAGE_VERSION + 1);
+    });
+
+    assert_eq!(setup.pair.try_migrate(), Err(Ok(PairError::UnsupportedStorageVersion)));
+}
+
+#[test]
+fn test_upgrade_requires_factory_auth() {
+    let setup = PairSetup::new();
+    let hash = setup.env.deployer().upload_contract_wasm(Bytes::new(&setup.env));
+
+    setup.pair.upgrade(&hash);
+
+    assert_eq!(setup.env.auths()[0].0, setup.factory.address);
+}
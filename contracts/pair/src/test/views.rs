@@ -2,7 +2,7 @@
 
 use crate::storage::{FeeState, PairStorage};
 use crate::{Pair, PairClient};
-use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, Env};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address, Env, U256};
 
 fn setup_test_env() -> (Env, PairClient<'static>) {
     let env = Env::default();
@@ -37,7 +37,7 @@ fn test_get_reserves_initialized() {
     let token_b = Address::generate(&env);
     let lp_token = Address::generate(&env);
 
-    pair_client.initialize(&factory, &token_a, &token_b, &lp_token);
+    pair_client.initialize(&factory, &token_a, &token_b, &lp_token, &super::default_fee_params());
 
     let (reserve_a, reserve_b, timestamp) = pair_client.get_reserves();
 
@@ -69,11 +69,10 @@ fn test_get_current_fee_bps_initialized_no_volatility() {
     let token_b = Address::generate(&env);
     let lp_token = Address::generate(&env);
 
-    pair_client.initialize(&factory, &token_a, &token_b, &lp_token);
+    pair_client.initialize(&factory, &token_a, &token_b, &lp_token, &super::default_fee_params());
 
-    // Also we need to simulate the fee state being set by initialization or default.
-    // Actually, `initialize` does NOT set the FeeState. It's set during `swap` when decaying/updating.
-    // If it's not set, `get_current_fee_bps` returns 30 (fallback).
+    // `initialize` writes the FeeState from the supplied params, so with no
+    // volatility yet the fee is the configured baseline.
     let fee = pair_client.get_current_fee_bps();
     assert_eq!(fee, 30);
 }
@@ -103,7 +102,7 @@ fn test_get_reserves_after_state_change() {
         block_timestamp_last: 12345,
         price_a_cumulative: 0,
         price_b_cumulative: 0,
-        k_last: 2000000,
+        k_last: U256::from_u32(&env, 2000000),
     };
 
     // Hack: use env to invoke bare function or just use pair_client which invokes `Pair` under the hood.
@@ -148,3 +147,100 @@ fn test_get_current_fee_bps_with_state() {
     let fee = pair_client.get_current_fee_bps();
     assert_eq!(fee, 100);
 }
+
+#[test]
+fn test_get_current_fee_bps_applies_stale_decay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, Pair);
+    let pair_client = PairClient::new(&env, &contract_id);
+
+    let fee_state = FeeState {
+        vol_accumulator: 1_000_000_000_000,
+        ema_alpha: 5_000_000_000_000,
+        baseline_fee_bps: 30,
+        min_fee_bps: 5,
+        max_fee_bps: 100,
+        ramp_up_multiplier: 2,
+        cooldown_divisor: 2,
+        last_fee_update: 0,
+        decay_threshold_blocks: 100,
+    };
+
+    env.as_contract(&contract_id, || {
+        crate::storage::set_fee_state(&env, &fee_state);
+    });
+
+    // Ten idle decay periods: vol = 1e12 / 2^10 ≈ 9.8e8, which contributes
+    // (9.8e8 * 2) / 1e10 = 0 bps on top of the baseline.
+    env.ledger().set_sequence_number(1_000);
+    assert_eq!(pair_client.get_current_fee_bps(), 30);
+
+    // The view must not persist the decayed accumulator.
+    env.as_contract(&contract_id, || {
+        let stored = crate::storage::get_fee_state(&env).unwrap();
+        assert_eq!(stored.vol_accumulator, 1_000_000_000_000);
+    });
+}
+
+#[test]
+fn test_initialize_writes_fee_state_from_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(42);
+
+    let contract_id = env.register_contract(None, Pair);
+    let pair_client = PairClient::new(&env, &contract_id);
+
+    let mut params = super::default_fee_params();
+    params.baseline_fee_bps = 50;
+    pair_client.initialize(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &params,
+    );
+
+    assert_eq!(pair_client.fee_params(), params);
+    assert_eq!(pair_client.get_current_fee_bps(), 50);
+    env.as_contract(&contract_id, || {
+        let fee_state = crate::storage::get_fee_state(&env).unwrap();
+        assert_eq!(fee_state.vol_accumulator, 0);
+        assert_eq!(fee_state.last_fee_update, 42);
+    });
+}
+
+#[test]
+fn test_set_fee_params_preserves_accumulator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, Pair);
+    let pair_client = PairClient::new(&env, &contract_id);
+    let factory = Address::generate(&env);
+
+    pair_client.initialize(
+        &factory,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &super::default_fee_params(),
+    );
+    env.as_contract(&contract_id, || {
+        let mut fee_state = crate::storage::get_fee_state(&env).unwrap();
+        fee_state.vol_accumulator = 777;
+        crate::storage::set_fee_state(&env, &fee_state);
+    });
+
+    let mut params = super::default_fee_params();
+    params.max_fee_bps = 250;
+    pair_client.set_fee_params(&params);
+    assert_eq!(env.auths()[0].0, factory);
+
+    assert_eq!(pair_client.fee_params(), params);
+    env.as_contract(&contract_id, || {
+        assert_eq!(crate::storage::get_fee_state(&env).unwrap().vol_accumulator, 777);
+    });
+}
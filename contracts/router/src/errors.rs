@@ -13,4 +13,6 @@ pub enum RouterError {
     ZeroAmount = 306,
     InsufficientLiquidity = 307,
     SlippageExceeded = 308,
+    Overflow = 309,
+    NotInitialized = 310,
 }
@@ -1,17 +1,28 @@
 #![allow(dead_code)]
 
-use crate::errors::RouterError;
-use soroban_sdk::{contractclient, Address, Env};
+use crate::{errors::RouterError, math};
+use soroban_sdk::{contractclient, Address, BytesN, Env, Vec};
+
+/// Basis point denominator.
+const BPS_DENOMINATOR: i128 = 10_000;
 
 #[contractclient(name = "FactoryClient")]
 #[allow(dead_code)]
 pub trait FactoryInterface {
-    fn get_pair(env: Env, token_a: Address, token_b: Address) -> Option<Address>;
+    fn get_pair(env: Env, token_a: Address, token_b: Address, fee_tier: u32) -> Option<Address>;
+    fn create_pair(
+        env: Env,
+        creator: Address,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+    ) -> Address;
 }
 
 #[contractclient(name = "PairClient")]
 #[allow(dead_code)]
 pub trait PairInterface {
+    fn mint(env: Env, to: Address) -> i128;
     fn burn(env: Env, to: Address) -> (i128, i128);
     fn lp_token(env: Env) -> Address;
     fn swap(env: Env, amount_a_out: i128, amount_b_out: i128, to: Address);
@@ -19,6 +30,20 @@ pub trait PairInterface {
     fn get_current_fee_bps(env: Env) -> u32;
 }
 
+#[contractclient(name = "LpTokenClient")]
+#[allow(dead_code)]
+pub trait LpTokenInterface {
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    );
+}
+
 #[contractclient(name = "TokenClient")]
 #[allow(dead_code)]
 pub trait TokenInterface {
@@ -26,6 +51,25 @@ pub trait TokenInterface {
     fn balance(env: Env, id: Address) -> i128;
 }
 
+/// Given some amount of an asset and pair reserves, returns the equivalent
+/// amount of the other asset at the current pool ratio (no fee applied).
+///
+/// Formula: amount_b = amount_a * reserve_b / reserve_a
+pub fn quote(
+    env: &Env,
+    amount_a: i128,
+    reserve_a: i128,
+    reserve_b: i128,
+) -> Result<i128, RouterError> {
+    if amount_a <= 0 {
+        return Err(RouterError::ZeroAmount);
+    }
+    if reserve_a <= 0 || reserve_b <= 0 {
+        return Err(RouterError::InsufficientLiquidity);
+    }
+    math::mul_div(env, amount_a, reserve_b, reserve_a).ok_or(RouterError::Overflow)
+}
+
 /// Computes output amount for an exact input swap using constant-product formula.
 ///
 /// Formula: amount_out = (amount_in * (10000 - fee_bps) * reserve_out) /
@@ -36,51 +80,193 @@ pub trait TokenInterface {
 /// * `reserve_in` - The reserve of the input token in the pair
 /// * `reserve_out` - The reserve of the output token in the pair
 /// * `fee_bps` - The fee in basis points (e.g., 30 = 0.3%)
-#[allow(dead_code)]
 pub fn get_amount_out(
-    _env: &Env,
-    _amount_in: i128,
-    _reserve_in: i128,
-    _reserve_out: i128,
-    _fee_bps: u32,
+    env: &Env,
+    amount_in: i128,
+    reserve_in: i128,
+    reserve_out: i128,
+    fee_bps: u32,
 ) -> Result<i128, RouterError> {
-    todo!()
+    if amount_in <= 0 {
+        return Err(RouterError::ZeroAmount);
+    }
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return Err(RouterError::InsufficientLiquidity);
+    }
+
+    let fee_factor = BPS_DENOMINATOR.checked_sub(fee_bps as i128).ok_or(RouterError::Overflow)?;
+    let amount_in_with_fee = amount_in.checked_mul(fee_factor).ok_or(RouterError::Overflow)?;
+    let denominator = reserve_in
+        .checked_mul(BPS_DENOMINATOR)
+        .ok_or(RouterError::Overflow)?
+        .checked_add(amount_in_with_fee)
+        .ok_or(RouterError::Overflow)?;
+
+    if denominator <= 0 {
+        return Err(RouterError::InsufficientLiquidity);
+    }
+
+    let amount_out = math::mul_div(env, amount_in_with_fee, reserve_out, denominator)
+        .ok_or(RouterError::Overflow)?;
+    if amount_out <= 0 {
+        return Err(RouterError::InsufficientOutputAmount);
+    }
+
+    Ok(amount_out)
 }
 
 /// Computes input amount required for an exact output swap.
 ///
-/// Formula: amount_in = (reserve_in * amount_out * 10000) /
-///                      ((reserve_out - amount_out) * (10000 - fee_bps)) + 1
-#[allow(dead_code)]
+/// Formula: amount_in = ceil((reserve_in * amount_out * 10000) /
+///                           ((reserve_out - amount_out) * (10000 - fee_bps)))
+///
+/// Rounding up yields the smallest input the pair's k check accepts.
 pub fn get_amount_in(
-    _env: &Env,
-    _amount_out: i128,
-    _reserve_in: i128,
-    _reserve_out: i128,
-    _fee_bps: u32,
+    env: &Env,
+    amount_out: i128,
+    reserve_in: i128,
+    reserve_out: i128,
+    fee_bps: u32,
 ) -> Result<i128, RouterError> {
-    todo!()
+    if amount_out <= 0 {
+        return Err(RouterError::ZeroAmount);
+    }
+    if reserve_in <= 0 || reserve_out <= 0 || amount_out >= reserve_out {
+        return Err(RouterError::InsufficientLiquidity);
+    }
+
+    let fee_factor = BPS_DENOMINATOR.checked_sub(fee_bps as i128).ok_or(RouterError::Overflow)?;
+    let scaled_out = amount_out.checked_mul(BPS_DENOMINATOR).ok_or(RouterError::Overflow)?;
+    let denominator =
+        (reserve_out - amount_out).checked_mul(fee_factor).ok_or(RouterError::Overflow)?;
+
+    if denominator <= 0 {
+        return Err(RouterError::InsufficientLiquidity);
+    }
+
+    math::mul_div_ceil(env, reserve_in, scaled_out, denominator).ok_or(RouterError::Overflow)
 }
 
 /// Sorts token addresses into canonical order (lexicographically).
 ///
 /// Returns tokens in the order (token_a, token_b) where token_a < token_b.
 /// This matches the ordering used by the Factory when creating pairs.
-#[allow(dead_code)]
 pub fn sort_tokens(
-    _token_a: &Address,
-    _token_b: &Address,
+    token_a: &Address,
+    token_b: &Address,
 ) -> Result<(Address, Address), RouterError> {
-    todo!()
+    if token_a == token_b {
+        return Err(RouterError::IdenticalTokens);
+    }
+    if token_a < token_b {
+        Ok((token_a.clone(), token_b.clone()))
+    } else {
+        Ok((token_b.clone(), token_a.clone()))
+    }
 }
 
-/// Get the pair address from the factory contract
+/// Get the pair address for `fee_tier` from the factory contract
 pub fn get_pair_address(
     env: &Env,
     factory: &Address,
     token_a: &Address,
     token_b: &Address,
+    fee_tier: u32,
 ) -> Result<Address, RouterError> {
     let factory_client = FactoryClient::new(env, factory);
-    factory_client.get_pair(token_a, token_b).ok_or(RouterError::PairNotFound)
+    factory_client.get_pair(token_a, token_b, &fee_tier).ok_or(RouterError::PairNotFound)
+}
+
+/// Checks that `path` has at least one hop and `fee_tiers` names the fee
+/// tier of every hop.
+pub fn check_path(path: &Vec<Address>, fee_tiers: &Vec<u32>) -> Result<(), RouterError> {
+    if path.len() < 2 || fee_tiers.len() != path.len() - 1 {
+        return Err(RouterError::InvalidPath);
+    }
+    Ok(())
+}
+
+/// Fetches a pair's reserves ordered as `(reserve_in, reserve_out)` for a
+/// `token_in -> token_out` hop, together with the pair's current fee.
+///
+/// The pair reports its fee with stale volatility already decayed, matching
+/// what `Pair::swap` charges in the same ledger.
+pub fn get_reserves_and_fee(
+    env: &Env,
+    pair: &Address,
+    token_in: &Address,
+    token_out: &Address,
+) -> Result<(i128, i128, u32), RouterError> {
+    let (token_0, _) = sort_tokens(token_in, token_out)?;
+    let pair_client = PairClient::new(env, pair);
+    let (reserve_a, reserve_b, _) = pair_client.get_reserves();
+    let fee_bps = pair_client.get_current_fee_bps();
+
+    if *token_in == token_0 {
+        Ok((reserve_a, reserve_b, fee_bps))
+    } else {
+        Ok((reserve_b, reserve_a, fee_bps))
+    }
+}
+
+/// Performs chained `get_amount_out` calculations over every hop in `path`.
+///
+/// Returns a vector whose first element is `amount_in` and whose `i + 1`-th
+/// element is the output of hop `i`, quoted against each pair's live fee.
+pub fn get_amounts_out(
+    env: &Env,
+    factory: &Address,
+    amount_in: i128,
+    path: &Vec<Address>,
+    fee_tiers: &Vec<u32>,
+) -> Result<Vec<i128>, RouterError> {
+    check_path(path, fee_tiers)?;
+
+    let mut amounts = Vec::new(env);
+    amounts.push_back(amount_in);
+
+    for i in 0..path.len() - 1 {
+        let token_in = path.get_unchecked(i);
+        let token_out = path.get_unchecked(i + 1);
+        let pair =
+            get_pair_address(env, factory, &token_in, &token_out, fee_tiers.get_unchecked(i))?;
+        let (reserve_in, reserve_out, fee_bps) =
+            get_reserves_and_fee(env, &pair, &token_in, &token_out)?;
+        let amount_out =
+            get_amount_out(env, amounts.get_unchecked(i), reserve_in, reserve_out, fee_bps)?;
+        amounts.push_back(amount_out);
+    }
+
+    Ok(amounts)
+}
+
+/// Performs chained `get_amount_in` calculations backwards over `path`.
+///
+/// Returns a vector whose last element is `amount_out` and whose `i`-th
+/// element is the input hop `i` requires, quoted against each pair's live fee.
+pub fn get_amounts_in(
+    env: &Env,
+    factory: &Address,
+    amount_out: i128,
+    path: &Vec<Address>,
+    fee_tiers: &Vec<u32>,
+) -> Result<Vec<i128>, RouterError> {
+    check_path(path, fee_tiers)?;
+
+    let mut amounts = Vec::new(env);
+    amounts.push_front(amount_out);
+
+    for i in (1..path.len()).rev() {
+        let token_in = path.get_unchecked(i - 1);
+        let token_out = path.get_unchecked(i);
+        let fee_tier = fee_tiers.get_unchecked(i - 1);
+        let pair = get_pair_address(env, factory, &token_in, &token_out, fee_tier)?;
+        let (reserve_in, reserve_out, fee_bps) =
+            get_reserves_and_fee(env, &pair, &token_in, &token_out)?;
+        let amount_in =
+            get_amount_in(env, amounts.first_unchecked(), reserve_in, reserve_out, fee_bps)?;
+        amounts.push_front(amount_in);
+    }
+
+    Ok(amounts)
 }
@@ -5,15 +5,17 @@ extern crate std;
 
 mod errors;
 mod helpers;
+mod math;
 mod storage;
 
 #[cfg(test)]
 mod test;
 
-use errors::RouterError;
-use helpers::{get_pair_address, PairClient};
+pub use errors::RouterError;
+use helpers::{get_pair_address, sort_tokens, FactoryClient, LpTokenClient, PairClient};
 use soroban_sdk::{contract, contractimpl, token::TokenClient, Address, Env, Vec};
-use storage::{get_factory, set_factory};
+pub use storage::Permit;
+use storage::{extend_instance_ttl, get_factory, set_factory};
 
 #[contract]
 pub struct Router;
@@ -23,66 +25,226 @@ impl Router {
     pub fn initialize(env: Env, factory: Address) {
         set_factory(&env, &factory);
     }
+
+    /// Extends the TTL of the router's instance storage. Permissionless, so
+    /// keepers can keep the router alive through quiet periods.
+    pub fn bump(env: Env) -> Result<(), RouterError> {
+        get_factory(&env).ok_or(RouterError::NotInitialized)?;
+        extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Swaps an exact amount of input tokens for as many output tokens as possible.
+    ///
+    /// Each hop in `path` is resolved through the factory in the matching
+    /// entry of `fee_tiers` and quoted with the pair's current dynamic fee. The input is transferred from `to` into the
+    /// first pair and every leg's output is sent straight to the next pair, so
+    /// only the final hop pays out to `to`.
+    ///
+    /// # Arguments
+    /// * `amount_in` - The exact amount of input tokens to spend
+    /// * `amount_out_min` - The minimum amount of output tokens to receive
+    /// * `path` - Vector of token addresses representing the swap route
+    /// * `fee_tiers` - Factory fee tier of each hop's pair, one per hop
+    /// * `to` - The address paying the input and receiving the output tokens
+    /// * `deadline` - Unix timestamp after which the transaction will revert
+    ///
+    /// # Returns
+    /// The input amount followed by the output amount of every hop.
     pub fn swap_exact_tokens_for_tokens(
-        _env: Env,
-        _amount_in: i128,
-        _amount_out_min: i128,
-        _path: Vec<Address>,
-        _to: Address,
-        _deadline: u64,
+        env: Env,
+        amount_in: i128,
+        amount_out_min: i128,
+        path: Vec<Address>,
+        fee_tiers: Vec<u32>,
+        to: Address,
+        deadline: u64,
     ) -> Result<Vec<i128>, RouterError> {
-        todo!()
+        if deadline < env.ledger().timestamp() {
+            return Err(RouterError::Expired);
+        }
+
+        if amount_in <= 0 {
+            return Err(RouterError::ZeroAmount);
+        }
+
+        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+
+        let amounts = helpers::get_amounts_out(&env, &factory, amount_in, &path, &fee_tiers)?;
+        if amounts.last_unchecked() < amount_out_min {
+            return Err(RouterError::InsufficientOutputAmount);
+        }
+
+        to.require_auth();
+
+        // Fund the first pair, then let each pair forward into the next.
+        let first_pair = get_pair_address(
+            &env,
+            &factory,
+            &path.get_unchecked(0),
+            &path.get_unchecked(1),
+            fee_tiers.get_unchecked(0),
+        )?;
+        TokenClient::new(&env, &path.get_unchecked(0)).transfer(&to, &first_pair, &amount_in);
+
+        Self::swap_along_path(&env, &factory, &amounts, &path, &fee_tiers, &to)?;
+
+        Ok(amounts)
     }
 
-    /// Swaps tokens to receive an exact amount of output tokens (not yet implemented).
+    /// Swaps as few input tokens as possible to receive an exact amount of output tokens.
+    ///
+    /// The required input is computed backwards through every pair in `path`,
+    /// each in the matching entry of `fee_tiers`, using its current dynamic fee. The input is transferred from `to`
+    /// into the first pair and each leg's output is forwarded to the next pair.
     ///
     /// # Arguments
     /// * `amount_out` - The exact amount of output tokens desired
     /// * `amount_in_max` - The maximum amount of input tokens to spend
     /// * `path` - Vector of token addresses representing the swap route
-    /// * `to` - The recipient address for output tokens
+    /// * `fee_tiers` - Factory fee tier of each hop's pair, one per hop
+    /// * `to` - The address paying the input and receiving the output tokens
     /// * `deadline` - Unix timestamp after which the transaction will revert
+    ///
+    /// # Returns
+    /// The input amount of every hop followed by the final output amount.
     pub fn swap_tokens_for_exact_tokens(
-        _env: Env,
-        _amount_out: i128,
-        _amount_in_max: i128,
-        _path: Vec<Address>,
-        _to: Address,
-        _deadline: u64,
+        env: Env,
+        amount_out: i128,
+        amount_in_max: i128,
+        path: Vec<Address>,
+        fee_tiers: Vec<u32>,
+        to: Address,
+        deadline: u64,
     ) -> Result<Vec<i128>, RouterError> {
-        todo!()
+        if deadline < env.ledger().timestamp() {
+            return Err(RouterError::Expired);
+        }
+
+        if amount_out <= 0 {
+            return Err(RouterError::ZeroAmount);
+        }
+
+        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+
+        let amounts = helpers::get_amounts_in(&env, &factory, amount_out, &path, &fee_tiers)?;
+        let amount_in = amounts.first_unchecked();
+        if amount_in > amount_in_max {
+            return Err(RouterError::ExcessiveInputAmount);
+        }
+
+        to.require_auth();
+
+        let first_pair = get_pair_address(
+            &env,
+            &factory,
+            &path.get_unchecked(0),
+            &path.get_unchecked(1),
+            fee_tiers.get_unchecked(0),
+        )?;
+        TokenClient::new(&env, &path.get_unchecked(0)).transfer(&to, &first_pair, &amount_in);
+
+        Self::swap_along_path(&env, &factory, &amounts, &path, &fee_tiers, &to)?;
+
+        Ok(amounts)
     }
 
-    /// Adds liquidity to a token pair (not yet implemented).
+    /// Adds liquidity to a token pair at the current pool ratio.
+    ///
+    /// If no pair exists yet in `fee_tier` it is created through the factory and the desired
+    /// amounts are deposited as-is, setting the initial price. Otherwise the
+    /// deposit is matched to the pair's reserve ratio, taking as much of the
+    /// desired amounts as possible while respecting the minimums. Both tokens
+    /// are transferred from `to` into the pair and the minted LP tokens are
+    /// credited to `to`.
     ///
     /// # Arguments
     /// * `token_a` - First token address
     /// * `token_b` - Second token address
+    /// * `fee_tier` - Factory fee tier of the pair
     /// * `amount_a_desired` - Desired amount of token_a to add
     /// * `amount_b_desired` - Desired amount of token_b to add
     /// * `amount_a_min` - Minimum amount of token_a to add
     /// * `amount_b_min` - Minimum amount of token_b to add
-    /// * `to` - Recipient of LP tokens
+    /// * `to` - Depositor and recipient of LP tokens; also the pair creator
+    ///   checked against the factory creation policy on first deposit
     /// * `deadline` - Unix timestamp after which the transaction will revert
+    ///
+    /// # Returns
+    /// `(amount_a, amount_b, liquidity)` actually deposited and minted.
     pub fn add_liquidity(
-        _env: Env,
-        _token_a: Address,
-        _token_b: Address,
-        _amount_a_desired: i128,
-        _amount_b_desired: i128,
-        _amount_a_min: i128,
-        _amount_b_min: i128,
-        _to: Address,
-        _deadline: u64,
+        env: Env,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+        amount_a_desired: i128,
+        amount_b_desired: i128,
+        amount_a_min: i128,
+        amount_b_min: i128,
+        to: Address,
+        deadline: u64,
     ) -> Result<(i128, i128, i128), RouterError> {
-        todo!()
+        if deadline < env.ledger().timestamp() {
+            return Err(RouterError::Expired);
+        }
+
+        if amount_a_desired <= 0 || amount_b_desired <= 0 {
+            return Err(RouterError::ZeroAmount);
+        }
+
+        if token_a == token_b {
+            return Err(RouterError::IdenticalTokens);
+        }
+
+        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+
+        // Create the pair on first deposit.
+        let pair_address = match get_pair_address(&env, &factory, &token_a, &token_b, fee_tier) {
+            Ok(pair) => pair,
+            Err(RouterError::PairNotFound) => {
+                FactoryClient::new(&env, &factory).create_pair(&to, &token_a, &token_b, &fee_tier)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let (reserve_a, reserve_b, _) =
+            helpers::get_reserves_and_fee(&env, &pair_address, &token_a, &token_b)?;
+
+        let (amount_a, amount_b) = if reserve_a == 0 && reserve_b == 0 {
+            (amount_a_desired, amount_b_desired)
+        } else {
+            let amount_b_optimal = helpers::quote(&env, amount_a_desired, reserve_a, reserve_b)?;
+            if amount_b_optimal <= amount_b_desired {
+                if amount_b_optimal < amount_b_min {
+                    return Err(RouterError::SlippageExceeded);
+                }
+                (amount_a_desired, amount_b_optimal)
+            } else {
+                let amount_a_optimal =
+                    helpers::quote(&env, amount_b_desired, reserve_b, reserve_a)?;
+                if amount_a_optimal > amount_a_desired || amount_a_optimal < amount_a_min {
+                    return Err(RouterError::SlippageExceeded);
+                }
+                (amount_a_optimal, amount_b_desired)
+            }
+        };
+
+        to.require_auth();
+
+        TokenClient::new(&env, &token_a).transfer(&to, &pair_address, &amount_a);
+        TokenClient::new(&env, &token_b).transfer(&to, &pair_address, &amount_b);
+
+        let liquidity = PairClient::new(&env, &pair_address).mint(&to);
+
+        Ok((amount_a, amount_b, liquidity))
     }
 
-    /// Removes liquidity from a token pair (not yet implemented).
+    /// Removes liquidity from a token pair.
     ///
     /// # Arguments
     /// * `token_a` - First token address
     /// * `token_b` - Second token address
+    /// * `fee_tier` - Factory fee tier of the pair
     /// * `liquidity` - Amount of LP tokens to burn
     /// * `amount_a_min` - Minimum amount of token_a to receive
     /// * `amount_b_min` - Minimum amount of token_b to receive
@@ -92,12 +254,136 @@ impl Router {
         env: Env,
         token_a: Address,
         token_b: Address,
+        fee_tier: u32,
+        liquidity: i128,
+        amount_a_min: i128,
+        amount_b_min: i128,
+        to: Address,
+        deadline: u64,
+    ) -> Result<(i128, i128), RouterError> {
+        let (pair_address, lp_token_address) =
+            Self::liquidity_pair(&env, &token_a, &token_b, fee_tier, liquidity, deadline)?;
+
+        // The user must provide authorization for the Router to transfer LP tokens
+        to.require_auth();
+
+        // Transfer LP tokens from 'to' to pair
+        let lp_token_client = TokenClient::new(&env, &lp_token_address);
+        lp_token_client.transfer(&to, &pair_address, &liquidity);
+
+        Self::burn_liquidity(&env, &pair_address, &to, amount_a_min, amount_b_min)
+    }
+
+    /// Removes liquidity using a signed LP token permit in place of a separate
+    /// `approve` transaction.
+    ///
+    /// The permit approves the router to spend `liquidity` LP tokens of `to`
+    /// until its expiration ledger; the router spends it immediately. `to`
+    /// must still authorize this call, which is what binds the slippage
+    /// minimums and deadline: the permit itself signs neither.
+    ///
+    /// A permit is public once broadcast and anyone may submit it to the LP
+    /// token first, which consumes its nonce. So if `to` has already allowed
+    /// the router `liquidity`, that allowance is spent and the permit is not
+    /// resubmitted.
+    ///
+    /// # Arguments
+    /// * `token_a` - First token address
+    /// * `token_b` - Second token address
+    /// * `fee_tier` - Factory fee tier of the pair
+    /// * `liquidity` - Amount of LP tokens to burn
+    /// * `amount_a_min` - Minimum amount of token_a to receive
+    /// * `amount_b_min` - Minimum amount of token_b to receive
+    /// * `to` - LP holder who signed the permit, and recipient of underlying tokens
+    /// * `deadline` - Unix timestamp after which the transaction will revert
+    /// * `permit` - `to`'s signed permit for `liquidity`, naming the router as spender
+    pub fn remove_liquidity_with_permit(
+        env: Env,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
         liquidity: i128,
         amount_a_min: i128,
         amount_b_min: i128,
         to: Address,
         deadline: u64,
+        permit: Permit,
     ) -> Result<(i128, i128), RouterError> {
+        let (pair_address, lp_token_address) =
+            Self::liquidity_pair(&env, &token_a, &token_b, fee_tier, liquidity, deadline)?;
+
+        to.require_auth();
+
+        let router = env.current_contract_address();
+        let lp_token = TokenClient::new(&env, &lp_token_address);
+        if lp_token.allowance(&to, &router) < liquidity {
+            LpTokenClient::new(&env, &lp_token_address).permit(
+                &to,
+                &router,
+                &liquidity,
+                &permit.expiration_ledger,
+                &permit.public_key,
+                &permit.signature,
+            );
+        }
+        lp_token.transfer_from(&router, &to, &pair_address, &liquidity);
+
+        Self::burn_liquidity(&env, &pair_address, &to, amount_a_min, amount_b_min)
+    }
+
+    /// Quotes an exact-input trade along `path` without executing it.
+    ///
+    /// Uses each pair's live reserves and its fee with idle decay applied, the
+    /// same fee `swap_exact_tokens_for_tokens` would be charged in this ledger.
+    ///
+    /// # Arguments
+    /// * `amount_in` - The exact amount of input tokens
+    /// * `path` - Vector of token addresses representing the swap route
+    /// * `fee_tiers` - Factory fee tier of each hop's pair, one per hop
+    ///
+    /// # Returns
+    /// The input amount followed by the output amount of every hop.
+    pub fn get_amounts_out(
+        env: Env,
+        amount_in: i128,
+        path: Vec<Address>,
+        fee_tiers: Vec<u32>,
+    ) -> Result<Vec<i128>, RouterError> {
+        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+        helpers::get_amounts_out(&env, &factory, amount_in, &path, &fee_tiers)
+    }
+
+    /// Quotes the input required to receive `amount_out` at the end of `path`.
+    ///
+    /// Uses each pair's live reserves and its fee with idle decay applied, the
+    /// same fee `swap_tokens_for_exact_tokens` would be charged in this ledger.
+    ///
+    /// # Arguments
+    /// * `amount_out` - The exact amount of output tokens desired
+    /// * `path` - Vector of token addresses representing the swap route
+    /// * `fee_tiers` - Factory fee tier of each hop's pair, one per hop
+    ///
+    /// # Returns
+    /// The input amount of every hop followed by the final output amount.
+    pub fn get_amounts_in(
+        env: Env,
+        amount_out: i128,
+        path: Vec<Address>,
+        fee_tiers: Vec<u32>,
+    ) -> Result<Vec<i128>, RouterError> {
+        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+        helpers::get_amounts_in(&env, &factory, amount_out, &path, &fee_tiers)
+    }
+
+    /// Validates a liquidity removal and returns the pair and its LP token.
+    fn liquidity_pair(
+        env: &Env,
+        token_a: &Address,
+        token_b: &Address,
+        fee_tier: u32,
+        liquidity: i128,
+        deadline: u64,
+    ) -> Result<(Address, Address), RouterError> {
         // Check deadline
         if deadline < env.ledger().timestamp() {
             return Err(RouterError::Expired);
@@ -114,26 +400,27 @@ impl Router {
         }
 
         // Get factory address
-        let factory = get_factory(&env).ok_or(RouterError::PairNotFound)?;
+        let factory = get_factory(env).ok_or(RouterError::PairNotFound)?;
 
         // Get pair address
-        let pair_address = get_pair_address(&env, &factory, &token_a, &token_b)?;
-
-        // Get pair contract client
-        let pair_client = PairClient::new(&env, &pair_address);
+        let pair_address = get_pair_address(env, &factory, token_a, token_b, fee_tier)?;
 
         // Get LP token address from pair
-        let lp_token_address = pair_client.lp_token();
+        let lp_token_address = PairClient::new(env, &pair_address).lp_token();
 
-        // The user must provide authorization for the Router to transfer LP tokens
-        to.require_auth();
-
-        // Transfer LP tokens from 'to' to pair
-        let lp_token_client = TokenClient::new(&env, &lp_token_address);
-        lp_token_client.transfer(&to, &pair_address, &liquidity);
+        Ok((pair_address, lp_token_address))
+    }
 
+    /// Burns the LP tokens already sent to `pair_address`, paying out to `to`.
+    fn burn_liquidity(
+        env: &Env,
+        pair_address: &Address,
+        to: &Address,
+        amount_a_min: i128,
+        amount_b_min: i128,
+    ) -> Result<(i128, i128), RouterError> {
         // Call Pair::burn(to) - this will burn LP tokens from the pair and transfer underlying tokens
-        let (amount_a, amount_b) = pair_client.burn(&to);
+        let (amount_a, amount_b) = PairClient::new(env, pair_address).burn(to);
 
         // Enforce minimum output amounts
         if amount_a < amount_a_min || amount_b < amount_b_min {
@@ -142,4 +429,47 @@ impl Router {
 
         Ok((amount_a, amount_b))
     }
+
+    /// Executes every hop in `path`, assuming the first pair is already funded.
+    ///
+    /// Each pair sends its output directly to the pair of the next hop; the
+    /// last pair pays out to `to`.
+    fn swap_along_path(
+        env: &Env,
+        factory: &Address,
+        amounts: &Vec<i128>,
+        path: &Vec<Address>,
+        fee_tiers: &Vec<u32>,
+        to: &Address,
+    ) -> Result<(), RouterError> {
+        let hops = path.len() - 1;
+        for i in 0..hops {
+            let token_in = path.get_unchecked(i);
+            let token_out = path.get_unchecked(i + 1);
+            let (token_0, _) = sort_tokens(&token_in, &token_out)?;
+            let amount_out = amounts.get_unchecked(i + 1);
+
+            let (amount_a_out, amount_b_out) =
+                if token_in == token_0 { (0, amount_out) } else { (amount_out, 0) };
+
+            let recipient = if i + 1 < hops {
+                let next_token = path.get_unchecked(i + 2);
+                get_pair_address(
+                    env,
+                    factory,
+                    &token_out,
+                    &next_token,
+                    fee_tiers.get_unchecked(i + 1),
+                )?
+            } else {
+                to.clone()
+            };
+
+            let pair =
+                get_pair_address(env, factory, &token_in, &token_out, fee_tiers.get_unchecked(i))?;
+            PairClient::new(env, &pair).swap(&amount_a_out, &amount_b_out, &recipient);
+        }
+
+        Ok(())
+    }
 }
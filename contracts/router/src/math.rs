@@ -0,0 +1,49 @@
+//! 256-bit multiply-divide helpers for reserve arithmetic.
+//!
+//! Products of 18-decimal reserves overflow `i128` long before the reserves
+//! themselves do, so every `a * b / c` the router quotes is computed with a
+//! U256 intermediate, matching the pair's own math.
+
+use soroban_sdk::{Env, U256};
+
+/// Lifts a non-negative `i128` into a `U256`. Returns `None` for negatives.
+fn to_u256(env: &Env, value: i128) -> Option<U256> {
+    if value < 0 {
+        return None;
+    }
+    Some(U256::from_u128(env, value as u128))
+}
+
+/// Narrows a `U256` back to `i128`. Returns `None` if it does not fit.
+fn from_u256(value: &U256) -> Option<i128> {
+    value.to_u128().and_then(|v| i128::try_from(v).ok())
+}
+
+/// Computes `floor(a * b / denominator)` with a 256-bit intermediate.
+///
+/// Returns `None` for a non-positive denominator, negative operands, or a
+/// quotient that does not fit in `i128`.
+pub fn mul_div(env: &Env, a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    let product = to_u256(env, a)?.mul(&to_u256(env, b)?);
+    from_u256(&product.div(&to_u256(env, denominator)?))
+}
+
+/// Computes `ceil(a * b / denominator)` with a 256-bit intermediate.
+///
+/// Same failure cases as [`mul_div`].
+pub fn mul_div_ceil(env: &Env, a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator <= 0 {
+        return None;
+    }
+    let product = to_u256(env, a)?.mul(&to_u256(env, b)?);
+    let denominator = to_u256(env, denominator)?;
+    let quotient = from_u256(&product.div(&denominator))?;
+    if product.rem_euclid(&denominator) == U256::from_u32(env, 0) {
+        Some(quotient)
+    } else {
+        quotient.checked_add(1)
+    }
+}
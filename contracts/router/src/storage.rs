@@ -1,14 +1,39 @@
-use soroban_sdk::{contracttype, Address, Env};
+use soroban_sdk::{contracttype, Address, BytesN, Env};
+
+pub const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day in 5s ledgers
+pub const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days in 5s ledgers
 
 #[contracttype]
 pub enum DataKey {
     Factory,
 }
 
+/// A signed LP token permit approving the router, as produced by the holder
+/// for `LpToken::permit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Permit {
+    pub expiration_ledger: u32,
+    pub public_key: BytesN<32>,
+    pub signature: BytesN<64>,
+}
+
+/// Writes the factory address and extends the instance TTL.
 pub fn set_factory(env: &Env, factory: &Address) {
     env.storage().instance().set(&DataKey::Factory, factory);
+    extend_instance_ttl(env);
 }
 
+/// Reads the factory address, extending the instance TTL if it is set.
 pub fn get_factory(env: &Env) -> Option<Address> {
-    env.storage().instance().get(&DataKey::Factory)
+    let factory = env.storage().instance().get(&DataKey::Factory);
+    if factory.is_some() {
+        extend_instance_ttl(env);
+    }
+    factory
+}
+
+/// Extend instance storage TTL to keep the router alive.
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 }
@@ -1,36 +1,74 @@
-use crate::{Router, RouterClient, RouterError};
+use crate::{
+    storage::{Permit, INSTANCE_BUMP_AMOUNT},
+    Router, RouterClient, RouterError,
+};
+use coralswap_lp_token::{LpToken, LpTokenClient, PermitMessage};
 use soroban_sdk::{
     contract, contractimpl, contracttype,
+    testutils::storage::Instance as _,
     testutils::Address as _,
     testutils::Ledger as _,
     token::{StellarAssetClient, TokenClient},
-    Address, Env,
+    xdr::ToXdr,
+    Address, BytesN, Env, String, Vec,
 };
 
 // ── Mock Contracts ────────────────────────────────────────────────────────────
 
-/// Minimal mock factory that stores and returns a pair address.
+/// The factory's standard 30 bps fee tier.
+const STANDARD_FEE_TIER: u32 = 30;
+const E18: i128 = 1_000_000_000_000_000_000;
+
+/// Minimal mock factory that stores and returns a pair address per fee tier.
 #[contract]
 pub struct MockFactory;
 
 #[contracttype]
 #[derive(Clone)]
 pub enum MFKey {
-    Pair(Address, Address),
+    Pair(Address, Address, u32),
+    NextPair,
 }
 
 #[contractimpl]
 impl MockFactory {
-    pub fn set_pair(env: Env, token_a: Address, token_b: Address, pair: Address) {
-        env.storage().instance().set(&MFKey::Pair(token_a, token_b), &pair);
+    pub fn set_pair(env: Env, token_a: Address, token_b: Address, fee_tier: u32, pair: Address) {
+        env.storage().instance().set(&MFKey::Pair(token_a, token_b, fee_tier), &pair);
+    }
+
+    pub fn get_pair(
+        env: Env,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+    ) -> Option<Address> {
+        env.storage().instance().get(&MFKey::Pair(token_a, token_b, fee_tier))
     }
 
-    pub fn get_pair(env: Env, token_a: Address, token_b: Address) -> Option<Address> {
-        env.storage().instance().get(&MFKey::Pair(token_a, token_b))
+    /// Queues the pre-registered mock pair handed out by the next `create_pair`.
+    pub fn set_next_pair(env: Env, pair: Address) {
+        env.storage().instance().set(&MFKey::NextPair, &pair);
+    }
+
+    pub fn create_pair(
+        env: Env,
+        _creator: Address,
+        token_a: Address,
+        token_b: Address,
+        fee_tier: u32,
+    ) -> Address {
+        let pair: Address = env.storage().instance().get(&MFKey::NextPair).unwrap();
+        let (token_0, token_1) =
+            if token_a < token_b { (token_a, token_b) } else { (token_b, token_a) };
+        MockPairClient::new(&env, &pair).set_tokens(&token_0, &token_1);
+        Self::set_pair(env.clone(), token_0.clone(), token_1.clone(), fee_tier, pair.clone());
+        Self::set_pair(env, token_1, token_0, fee_tier, pair.clone());
+        pair
     }
 }
 
-/// Minimal mock pair that returns pre-configured burn amounts and LP token.
+/// Minimal mock pair that returns pre-configured burn amounts and LP token,
+/// and performs unchecked optimistic swaps against its token balances.
 #[contract]
 pub struct MockPair;
 
@@ -40,6 +78,11 @@ pub enum MPKey {
     LpToken,
     AmountA,
     AmountB,
+    TokenA,
+    TokenB,
+    ReserveA,
+    ReserveB,
+    FeeBps,
 }
 
 #[contractimpl]
@@ -62,6 +105,61 @@ impl MockPair {
         let b: i128 = env.storage().instance().get(&MPKey::AmountB).unwrap();
         (a, b)
     }
+
+    pub fn set_tokens(env: Env, token_a: Address, token_b: Address) {
+        env.storage().instance().set(&MPKey::TokenA, &token_a);
+        env.storage().instance().set(&MPKey::TokenB, &token_b);
+    }
+
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        env.storage().instance().set(&MPKey::FeeBps, &fee_bps);
+    }
+
+    /// Snapshots the pair's current token balances as its reserves.
+    pub fn sync(env: Env) {
+        let contract = env.current_contract_address();
+        let token_a: Address = env.storage().instance().get(&MPKey::TokenA).unwrap();
+        let token_b: Address = env.storage().instance().get(&MPKey::TokenB).unwrap();
+        let balance_a = TokenClient::new(&env, &token_a).balance(&contract);
+        let balance_b = TokenClient::new(&env, &token_b).balance(&contract);
+        env.storage().instance().set(&MPKey::ReserveA, &balance_a);
+        env.storage().instance().set(&MPKey::ReserveB, &balance_b);
+    }
+
+    pub fn get_reserves(env: Env) -> (i128, i128, u64) {
+        let a: i128 = env.storage().instance().get(&MPKey::ReserveA).unwrap_or(0);
+        let b: i128 = env.storage().instance().get(&MPKey::ReserveB).unwrap_or(0);
+        (a, b, env.ledger().timestamp())
+    }
+
+    pub fn get_current_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&MPKey::FeeBps).unwrap_or(30)
+    }
+
+    /// Mints `min(deposit_a, deposit_b)` mock liquidity and syncs reserves.
+    pub fn mint(env: Env, _to: Address) -> i128 {
+        let contract = env.current_contract_address();
+        let token_a: Address = env.storage().instance().get(&MPKey::TokenA).unwrap();
+        let token_b: Address = env.storage().instance().get(&MPKey::TokenB).unwrap();
+        let (reserve_a, reserve_b, _) = Self::get_reserves(env.clone());
+        let deposit_a = TokenClient::new(&env, &token_a).balance(&contract) - reserve_a;
+        let deposit_b = TokenClient::new(&env, &token_b).balance(&contract) - reserve_b;
+        Self::sync(env);
+        deposit_a.min(deposit_b)
+    }
+
+    pub fn swap(env: Env, amount_a_out: i128, amount_b_out: i128, to: Address) {
+        let contract = env.current_contract_address();
+        let token_a: Address = env.storage().instance().get(&MPKey::TokenA).unwrap();
+        let token_b: Address = env.storage().instance().get(&MPKey::TokenB).unwrap();
+        if amount_a_out > 0 {
+            TokenClient::new(&env, &token_a).transfer(&contract, &to, &amount_a_out);
+        }
+        if amount_b_out > 0 {
+            TokenClient::new(&env, &token_b).transfer(&contract, &to, &amount_b_out);
+        }
+        Self::sync(env);
+    }
 }
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
@@ -107,7 +205,7 @@ fn setup_full_env() -> (
 
     // Wire up: Router -> Factory -> Pair -> LP Token
     router_client.initialize(&factory_addr);
-    mock_factory_client.set_pair(&token_a, &token_b, &pair_addr);
+    mock_factory_client.set_pair(&token_a, &token_b, &STANDARD_FEE_TIER, &pair_addr);
     mock_pair_client.set_lp_token(&lp_token_addr);
     mock_pair_client.set_burn_amounts(&500, &1000);
 
@@ -119,21 +217,725 @@ fn setup_full_env() -> (
     (env, router_client, token_a, token_b, to, deadline, mock_pair_client, lp_token_addr, pair_addr)
 }
 
-// ── Placeholder tests (other functions still todo) ────────────────────────────
+/// Mirrors the router's constant-product quote for expected-value assertions.
+fn quote_out(amount_in: i128, reserve_in: i128, reserve_out: i128, fee_bps: u32) -> i128 {
+    let amount_in_with_fee = amount_in * (10_000 - fee_bps as i128);
+    amount_in_with_fee * reserve_out / (reserve_in * 10_000 + amount_in_with_fee)
+}
+
+/// Mirrors the router's exact-output quote for expected-value assertions.
+fn quote_in(amount_out: i128, reserve_in: i128, reserve_out: i128, fee_bps: u32) -> i128 {
+    let numerator = reserve_in * amount_out * 10_000;
+    let denominator = (reserve_out - amount_out) * (10_000 - fee_bps as i128);
+    (numerator + denominator - 1) / denominator
+}
+
+/// Routes every hop of `path` through the standard fee tier.
+fn tiers(path: &Vec<Address>) -> Vec<u32> {
+    let mut tiers = Vec::new(path.env());
+    for _ in 1..path.len() {
+        tiers.push_back(STANDARD_FEE_TIER);
+    }
+    tiers
+}
+
+/// Deploys a mock pair for `(token_x, token_y)` seeded with `reserve` of each
+/// token and registers it with the mock factory in `fee_tier`, in both
+/// directions.
+fn create_mock_pair(
+    env: &Env,
+    factory: &MockFactoryClient,
+    token_x: &Address,
+    token_y: &Address,
+    fee_tier: u32,
+    reserve: i128,
+) -> MockPairClient<'static> {
+    let pair_addr = env.register_contract(None, MockPair);
+    let pair = MockPairClient::new(env, &pair_addr);
+
+    let (token_a, token_b) = if token_x < token_y {
+        (token_x.clone(), token_y.clone())
+    } else {
+        (token_y.clone(), token_x.clone())
+    };
+    pair.set_tokens(&token_a, &token_b);
+
+    StellarAssetClient::new(env, token_x).mint(&pair_addr, &reserve);
+    StellarAssetClient::new(env, token_y).mint(&pair_addr, &reserve);
+    pair.sync();
+
+    factory.set_pair(token_x, token_y, &fee_tier, &pair_addr);
+    factory.set_pair(token_y, token_x, &fee_tier, &pair_addr);
+
+    pair
+}
+
+/// Sets up a Router wired to two mock pairs forming the path X -> Y -> Z.
+///
+/// Returns (env, router_client, factory_client, [x, y, z], pair_xy, pair_yz,
+///          user, deadline). The user holds 100_000 of token X.
+#[allow(clippy::type_complexity)]
+fn setup_swap_env() -> (
+    Env,
+    RouterClient<'static>,
+    MockFactoryClient<'static>,
+    [Address; 3],
+    MockPairClient<'static>,
+    MockPairClient<'static>,
+    Address,
+    u64,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let router_addr = env.register_contract(None, Router);
+    let router_client = RouterClient::new(&env, &router_addr);
+
+    let factory_addr = env.register_contract(None, MockFactory);
+    let factory_client = MockFactoryClient::new(&env, &factory_addr);
+    router_client.initialize(&factory_addr);
+
+    let admin = Address::generate(&env);
+    let token_x = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_y = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_z = env.register_stellar_asset_contract_v2(admin).address();
+
+    let pair_xy =
+        create_mock_pair(&env, &factory_client, &token_x, &token_y, STANDARD_FEE_TIER, 1_000_000);
+    let pair_yz =
+        create_mock_pair(&env, &factory_client, &token_y, &token_z, STANDARD_FEE_TIER, 1_000_000);
+
+    let user = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_x).mint(&user, &100_000);
+
+    let deadline = env.ledger().timestamp() + 1000;
+
+    (
+        env,
+        router_client,
+        factory_client,
+        [token_x, token_y, token_z],
+        pair_xy,
+        pair_yz,
+        user,
+        deadline,
+    )
+}
+
+// ── swap_exact_tokens_for_tokens tests ────────────────────────────────────────
+
+#[test]
+fn test_swap_exact_tokens_single_hop() {
+    let (env, router, _factory, [x, y, _z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y.clone()]);
+    let amounts =
+        router.swap_exact_tokens_for_tokens(&10_000, &0, &path, &tiers(&path), &user, &deadline);
+
+    let expected_out = quote_out(10_000, 1_000_000, 1_000_000, 30);
+    assert_eq!(amounts, Vec::from_array(&env, [10_000, expected_out]));
+
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 90_000);
+    assert_eq!(TokenClient::new(&env, &y).balance(&user), expected_out);
+
+    let (reserve_a, reserve_b, _) = pair_xy.get_reserves();
+    assert_eq!(reserve_a + reserve_b, 2_000_000 + 10_000 - expected_out);
+}
+
+#[test]
+fn test_swap_exact_tokens_multi_hop_forwards_between_pairs() {
+    let (env, router, _factory, [x, y, z], _pair_xy, pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y.clone(), z.clone()]);
+    let amounts =
+        router.swap_exact_tokens_for_tokens(&10_000, &0, &path, &tiers(&path), &user, &deadline);
+
+    let hop_1 = quote_out(10_000, 1_000_000, 1_000_000, 30);
+    let hop_2 = quote_out(hop_1, 1_000_000, 1_000_000, 30);
+    assert_eq!(amounts, Vec::from_array(&env, [10_000, hop_1, hop_2]));
+
+    // Intermediate output went straight into the second pair, never to the user.
+    assert_eq!(TokenClient::new(&env, &y).balance(&user), 0);
+    assert_eq!(TokenClient::new(&env, &y).balance(&pair_yz.address), 1_000_000 + hop_1);
+    assert_eq!(TokenClient::new(&env, &z).balance(&user), hop_2);
+}
+
+#[test]
+fn test_swap_exact_tokens_uses_live_pair_fee() {
+    let (env, router, _factory, [x, y, _z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    pair_xy.set_fee_bps(&100);
+
+    let path = Vec::from_array(&env, [x, y]);
+    let amounts =
+        router.swap_exact_tokens_for_tokens(&10_000, &0, &path, &tiers(&path), &user, &deadline);
+
+    assert_eq!(amounts.get_unchecked(1), quote_out(10_000, 1_000_000, 1_000_000, 100));
+    assert!(amounts.get_unchecked(1) < quote_out(10_000, 1_000_000, 1_000_000, 30));
+}
+
+#[test]
+fn test_swap_exact_tokens_insufficient_output_amount() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y]);
+    let expected_out = quote_out(10_000, 1_000_000, 1_000_000, 30);
+    let result = router.try_swap_exact_tokens_for_tokens(
+        &10_000,
+        &(expected_out + 1),
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::InsufficientOutputAmount)));
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 100_000);
+}
+
+#[test]
+fn test_swap_exact_tokens_expired_deadline() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, _deadline) = setup_swap_env();
+    env.ledger().set_timestamp(2000);
+
+    let path = Vec::from_array(&env, [x, y]);
+    let result =
+        router.try_swap_exact_tokens_for_tokens(&10_000, &0, &path, &tiers(&path), &user, &1000);
+
+    assert_eq!(result, Err(Ok(RouterError::Expired)));
+}
+
+#[test]
+fn test_swap_exact_tokens_invalid_path() {
+    let (env, router, _factory, [x, _y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x]);
+    let result = router.try_swap_exact_tokens_for_tokens(
+        &10_000,
+        &0,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::InvalidPath)));
+}
+
+#[test]
+fn test_swap_exact_tokens_zero_amount() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x, y]);
+    let result =
+        router.try_swap_exact_tokens_for_tokens(&0, &0, &path, &tiers(&path), &user, &deadline);
+
+    assert_eq!(result, Err(Ok(RouterError::ZeroAmount)));
+}
+
+#[test]
+fn test_swap_exact_tokens_routes_each_hop_through_its_fee_tier() {
+    let (env, router, factory, [x, y, z], pair_xy, pair_yz, user, deadline) = setup_swap_env();
+    let stable_xy = create_mock_pair(&env, &factory, &x, &y, 5, 4_000_000);
+    stable_xy.set_fee_bps(&5);
+
+    let path = Vec::from_array(&env, [x, y.clone(), z]);
+    let amounts = router.swap_exact_tokens_for_tokens(
+        &10_000,
+        &0,
+        &path,
+        &Vec::from_array(&env, [5, STANDARD_FEE_TIER]),
+        &user,
+        &deadline,
+    );
+
+    let hop_1 = quote_out(10_000, 4_000_000, 4_000_000, 5);
+    let hop_2 = quote_out(hop_1, 1_000_000, 1_000_000, 30);
+    assert_eq!(amounts, Vec::from_array(&env, [10_000, hop_1, hop_2]));
+    assert_eq!(pair_xy.get_reserves().0 + pair_xy.get_reserves().1, 2_000_000);
+    assert_eq!(TokenClient::new(&env, &y).balance(&pair_yz.address), 1_000_000 + hop_1);
+}
+
+#[test]
+fn test_swap_exact_tokens_rejects_fee_tier_count_mismatch() {
+    let (env, router, _factory, [x, y, z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x, y, z]);
+    let fee_tiers = Vec::from_array(&env, [STANDARD_FEE_TIER]);
+    let result =
+        router.try_swap_exact_tokens_for_tokens(&10_000, &0, &path, &fee_tiers, &user, &deadline);
+
+    assert_eq!(result, Err(Ok(RouterError::InvalidPath)));
+}
+
+#[test]
+fn test_swap_exact_tokens_pair_not_found_in_fee_tier() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    // X/Y only exists in the standard tier.
+    let path = Vec::from_array(&env, [x, y]);
+    let fee_tiers = Vec::from_array(&env, [100]);
+    let result =
+        router.try_swap_exact_tokens_for_tokens(&10_000, &0, &path, &fee_tiers, &user, &deadline);
+
+    assert_eq!(result, Err(Ok(RouterError::PairNotFound)));
+}
+
+#[test]
+fn test_swap_exact_tokens_pair_not_found() {
+    let (env, router, _factory, [x, _y, z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    // No X/Z pair is registered.
+    let path = Vec::from_array(&env, [x, z]);
+    let result = router.try_swap_exact_tokens_for_tokens(
+        &10_000,
+        &0,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::PairNotFound)));
+}
+
+// ── swap_tokens_for_exact_tokens tests ────────────────────────────────────────
+
+#[test]
+fn test_swap_for_exact_tokens_single_hop() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y.clone()]);
+    let amounts = router.swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    let expected_in = quote_in(5_000, 1_000_000, 1_000_000, 30);
+    assert_eq!(amounts, Vec::from_array(&env, [expected_in, 5_000]));
+
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 100_000 - expected_in);
+    assert_eq!(TokenClient::new(&env, &y).balance(&user), 5_000);
+}
+
+#[test]
+fn test_swap_for_exact_tokens_multi_hop() {
+    let (env, router, _factory, [x, y, z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y.clone(), z.clone()]);
+    let amounts = router.swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    let hop_2_in = quote_in(5_000, 1_000_000, 1_000_000, 30);
+    let hop_1_in = quote_in(hop_2_in, 1_000_000, 1_000_000, 30);
+    assert_eq!(amounts, Vec::from_array(&env, [hop_1_in, hop_2_in, 5_000]));
+
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 100_000 - hop_1_in);
+    assert_eq!(TokenClient::new(&env, &y).balance(&user), 0);
+    assert_eq!(TokenClient::new(&env, &z).balance(&user), 5_000);
+}
+
+#[test]
+fn test_swap_for_exact_tokens_uses_live_pair_fee() {
+    let (env, router, _factory, [x, y, _z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    pair_xy.set_fee_bps(&100);
+
+    let path = Vec::from_array(&env, [x, y]);
+    let amounts = router.swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(amounts.get_unchecked(0), quote_in(5_000, 1_000_000, 1_000_000, 100));
+}
+
+#[test]
+fn test_swap_for_exact_tokens_excessive_input_amount() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x.clone(), y]);
+    let expected_in = quote_in(5_000, 1_000_000, 1_000_000, 30);
+    let result = router.try_swap_tokens_for_exact_tokens(
+        &5_000,
+        &(expected_in - 1),
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::ExcessiveInputAmount)));
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 100_000);
+}
+
+#[test]
+fn test_swap_for_exact_tokens_output_exceeds_reserve() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x, y]);
+    let result = router.try_swap_tokens_for_exact_tokens(
+        &1_000_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::InsufficientLiquidity)));
+}
+
+#[test]
+fn test_swap_for_exact_tokens_expired_deadline() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, _deadline) = setup_swap_env();
+    env.ledger().set_timestamp(2000);
+
+    let path = Vec::from_array(&env, [x, y]);
+    let result = router.try_swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &1000,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::Expired)));
+}
+
+#[test]
+fn test_swap_for_exact_tokens_invalid_path() {
+    let (env, router, _factory, [x, _y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let path = Vec::from_array(&env, [x]);
+    let result = router.try_swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::InvalidPath)));
+}
+
+#[test]
+fn test_get_amount_in_round_trips_through_get_amount_out() {
+    let env = Env::default();
+
+    for fee_bps in [0u32, 5, 30, 100] {
+        let amount_in =
+            crate::helpers::get_amount_in(&env, 12_345, 3_000_000, 7_000_000, fee_bps).unwrap();
+        let amount_out =
+            crate::helpers::get_amount_out(&env, amount_in, 3_000_000, 7_000_000, fee_bps).unwrap();
+        // Rounding up the input must always cover the requested output.
+        assert!(amount_out >= 12_345, "fee {} bps: {} < 12_345", fee_bps, amount_out);
+    }
+}
+
+#[test]
+fn test_get_amount_in_is_the_exact_minimum() {
+    let env = Env::default();
+
+    // 1000 * 1000 * 10_000 divides evenly by 1000 * 10_000: no rounding needed.
+    assert_eq!(crate::helpers::get_amount_in(&env, 1_000, 1_000, 2_000, 0), Ok(1_000));
+    assert_eq!(crate::helpers::get_amount_out(&env, 1_000, 1_000, 2_000, 0), Ok(1_000));
+    assert_eq!(crate::helpers::get_amount_out(&env, 999, 1_000, 2_000, 0), Ok(999));
+}
+
+#[test]
+fn test_quotes_on_18_decimal_pools_do_not_overflow() {
+    let env = Env::default();
+    let reserve = 1_000_000 * E18;
+    assert!((E18 * 9_970).checked_mul(reserve).is_none());
+
+    let amount_out = crate::helpers::get_amount_out(&env, E18, reserve, reserve, 30).unwrap();
+    assert!(amount_out > 0 && amount_out < E18);
+    let amount_in = crate::helpers::get_amount_in(&env, amount_out, reserve, reserve, 30).unwrap();
+    assert!(amount_in <= E18);
+    assert_eq!(crate::helpers::quote(&env, E18, reserve, 2 * reserve), Ok(2 * E18));
+}
+
+#[test]
+fn test_router_handles_18_decimal_pool() {
+    let (env, router, factory, [x, _y, z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    let reserve = 1_000_000 * E18;
+    create_mock_pair(&env, &factory, &x, &z, STANDARD_FEE_TIER, reserve);
+    StellarAssetClient::new(&env, &x).mint(&user, &(10 * E18));
+    StellarAssetClient::new(&env, &z).mint(&user, &(10 * E18));
+
+    let (amount_a, amount_b, _) =
+        router.add_liquidity(&x, &z, &STANDARD_FEE_TIER, &E18, &E18, &0, &0, &user, &deadline);
+    assert_eq!((amount_a, amount_b), (E18, E18));
+
+    let path = Vec::from_array(&env, [x, z]);
+    let quoted = router.get_amounts_out(&E18, &path, &tiers(&path));
+    let executed =
+        router.swap_exact_tokens_for_tokens(&E18, &0, &path, &tiers(&path), &user, &deadline);
+    assert_eq!(executed, quoted);
+
+    let quoted = router.get_amounts_in(&E18, &path, &tiers(&path));
+    let executed = router.swap_tokens_for_exact_tokens(
+        &E18,
+        &(2 * E18),
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+    assert_eq!(executed, quoted);
+}
+
+// ── quote view tests ──────────────────────────────────────────────────────────
+
+#[test]
+fn test_get_amounts_out_matches_executed_swap() {
+    let (env, router, _factory, [x, y, z], _pair_xy, pair_yz, user, deadline) = setup_swap_env();
+    pair_yz.set_fee_bps(&75);
+
+    let path = Vec::from_array(&env, [x, y, z]);
+    let quoted = router.get_amounts_out(&10_000, &path, &tiers(&path));
+
+    let hop_1 = quote_out(10_000, 1_000_000, 1_000_000, 30);
+    let hop_2 = quote_out(hop_1, 1_000_000, 1_000_000, 75);
+    assert_eq!(quoted, Vec::from_array(&env, [10_000, hop_1, hop_2]));
+
+    let executed =
+        router.swap_exact_tokens_for_tokens(&10_000, &0, &path, &tiers(&path), &user, &deadline);
+    assert_eq!(quoted, executed);
+}
+
+#[test]
+fn test_get_amounts_in_matches_executed_swap() {
+    let (env, router, _factory, [x, y, z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    pair_xy.set_fee_bps(&75);
+
+    let path = Vec::from_array(&env, [x, y, z]);
+    let quoted = router.get_amounts_in(&5_000, &path, &tiers(&path));
+
+    let hop_2_in = quote_in(5_000, 1_000_000, 1_000_000, 30);
+    let hop_1_in = quote_in(hop_2_in, 1_000_000, 1_000_000, 75);
+    assert_eq!(quoted, Vec::from_array(&env, [hop_1_in, hop_2_in, 5_000]));
+
+    let executed = router.swap_tokens_for_exact_tokens(
+        &5_000,
+        &100_000,
+        &path,
+        &tiers(&path),
+        &user,
+        &deadline,
+    );
+    assert_eq!(quoted, executed);
+}
 
 #[test]
-fn test_placeholder_swap_exact_in() {
-    let _env = Env::default();
+fn test_get_amounts_quote_the_requested_fee_tier() {
+    let (env, router, factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    let volatile_xy = create_mock_pair(&env, &factory, &x, &y, 100, 500_000);
+    volatile_xy.set_fee_bps(&100);
+
+    let path = Vec::from_array(&env, [x, y]);
+    let fee_tiers = Vec::from_array(&env, [100]);
+    let quoted_out = router.get_amounts_out(&10_000, &path, &fee_tiers);
+    assert_eq!(quoted_out.get_unchecked(1), quote_out(10_000, 500_000, 500_000, 100));
+    let quoted_in = router.get_amounts_in(&5_000, &path, &fee_tiers);
+    assert_eq!(quoted_in.get_unchecked(0), quote_in(5_000, 500_000, 500_000, 100));
+
+    let executed =
+        router.swap_tokens_for_exact_tokens(&5_000, &100_000, &path, &fee_tiers, &user, &deadline);
+    assert_eq!(executed, quoted_in);
 }
 
 #[test]
-fn test_placeholder_swap_tokens_for_exact_tokens() {
-    let _env = Env::default();
+fn test_get_amounts_views_reject_invalid_path() {
+    let (env, router, _factory, [x, _y, _z], _pair_xy, _pair_yz, _user, _deadline) =
+        setup_swap_env();
+
+    let path = Vec::from_array(&env, [x]);
+    assert_eq!(
+        router.try_get_amounts_out(&10_000, &path, &tiers(&path)),
+        Err(Ok(RouterError::InvalidPath))
+    );
+    assert_eq!(
+        router.try_get_amounts_in(&10_000, &path, &tiers(&path)),
+        Err(Ok(RouterError::InvalidPath))
+    );
+}
+
+// ── add_liquidity tests ───────────────────────────────────────────────────────
+
+#[test]
+fn test_add_liquidity_matches_pool_ratio_for_token_b() {
+    let (env, router, _factory, [x, y, _z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    StellarAssetClient::new(&env, &y).mint(&user, &100_000);
+
+    // Pool is 1:1, so only 10_000 of the 20_000 desired Y is used.
+    let (amount_a, amount_b, liquidity) = router.add_liquidity(
+        &x,
+        &y,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &20_000,
+        &9_000,
+        &9_000,
+        &user,
+        &deadline,
+    );
+
+    assert_eq!((amount_a, amount_b, liquidity), (10_000, 10_000, 10_000));
+    assert_eq!(TokenClient::new(&env, &x).balance(&user), 90_000);
+    assert_eq!(TokenClient::new(&env, &y).balance(&user), 90_000);
+    let (reserve_a, reserve_b, _) = pair_xy.get_reserves();
+    assert_eq!((reserve_a, reserve_b), (1_010_000, 1_010_000));
 }
 
 #[test]
-fn test_placeholder_add_liquidity() {
-    let _env = Env::default();
+fn test_add_liquidity_matches_pool_ratio_for_token_a() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    StellarAssetClient::new(&env, &y).mint(&user, &100_000);
+
+    // Y is the constraining side: only 5_000 of the 10_000 desired X is used.
+    let (amount_a, amount_b, _) =
+        router.add_liquidity(&x, &y, &STANDARD_FEE_TIER, &10_000, &5_000, &0, &0, &user, &deadline);
+
+    assert_eq!((amount_a, amount_b), (5_000, 5_000));
+}
+
+#[test]
+fn test_add_liquidity_below_minimum_reverts() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    StellarAssetClient::new(&env, &y).mint(&user, &100_000);
+
+    let result = router.try_add_liquidity(
+        &x,
+        &y,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &5_000,
+        &6_000,
+        &0,
+        &user,
+        &deadline,
+    );
+    assert_eq!(result, Err(Ok(RouterError::SlippageExceeded)));
+
+    let result = router.try_add_liquidity(
+        &x,
+        &y,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &20_000,
+        &0,
+        &11_000,
+        &user,
+        &deadline,
+    );
+    assert_eq!(result, Err(Ok(RouterError::SlippageExceeded)));
+}
+
+#[test]
+fn test_add_liquidity_creates_missing_pair() {
+    let (env, router, factory, [x, _y, z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    StellarAssetClient::new(&env, &z).mint(&user, &100_000);
+
+    let new_pair = env.register_contract(None, MockPair);
+    factory.set_next_pair(&new_pair);
+    assert!(factory.get_pair(&x, &z, &STANDARD_FEE_TIER).is_none());
+
+    // First deposit sets the price, so desired amounts are taken as-is.
+    let (amount_a, amount_b, _) = router.add_liquidity(
+        &x,
+        &z,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &40_000,
+        &10_000,
+        &40_000,
+        &user,
+        &deadline,
+    );
+
+    assert_eq!((amount_a, amount_b), (10_000, 40_000));
+    assert_eq!(factory.get_pair(&x, &z, &STANDARD_FEE_TIER), Some(new_pair.clone()));
+    assert_eq!(TokenClient::new(&env, &x).balance(&new_pair), 10_000);
+    assert_eq!(TokenClient::new(&env, &z).balance(&new_pair), 40_000);
+}
+
+#[test]
+fn test_add_liquidity_creates_pair_in_requested_fee_tier() {
+    let (env, router, factory, [x, y, _z], pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+    StellarAssetClient::new(&env, &y).mint(&user, &100_000);
+
+    let stable_pair = env.register_contract(None, MockPair);
+    factory.set_next_pair(&stable_pair);
+
+    router.add_liquidity(&x, &y, &5, &10_000, &10_000, &0, &0, &user, &deadline);
+
+    assert_eq!(factory.get_pair(&x, &y, &5), Some(stable_pair.clone()));
+    assert_eq!(factory.get_pair(&x, &y, &STANDARD_FEE_TIER), Some(pair_xy.address.clone()));
+    assert_eq!(TokenClient::new(&env, &x).balance(&stable_pair), 10_000);
+    assert_eq!(pair_xy.get_reserves().0, 1_000_000);
+}
+
+#[test]
+fn test_add_liquidity_expired_deadline() {
+    let (env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, _deadline) = setup_swap_env();
+    env.ledger().set_timestamp(2000);
+
+    let result = router.try_add_liquidity(
+        &x,
+        &y,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &10_000,
+        &0,
+        &0,
+        &user,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(RouterError::Expired)));
+}
+
+#[test]
+fn test_add_liquidity_identical_tokens() {
+    let (_env, router, _factory, [x, _y, _z], _pair_xy, _pair_yz, user, deadline) =
+        setup_swap_env();
+
+    let result = router.try_add_liquidity(
+        &x,
+        &x,
+        &STANDARD_FEE_TIER,
+        &10_000,
+        &10_000,
+        &0,
+        &0,
+        &user,
+        &deadline,
+    );
+    assert_eq!(result, Err(Ok(RouterError::IdenticalTokens)));
+}
+
+#[test]
+fn test_add_liquidity_zero_amount() {
+    let (_env, router, _factory, [x, y, _z], _pair_xy, _pair_yz, user, deadline) = setup_swap_env();
+
+    let result =
+        router.try_add_liquidity(&x, &y, &STANDARD_FEE_TIER, &0, &10_000, &0, &0, &user, &deadline);
+    assert_eq!(result, Err(Ok(RouterError::ZeroAmount)));
 }
 
 // ── remove_liquidity tests ────────────────────────────────────────────────────
@@ -157,6 +959,7 @@ fn test_remove_liquidity_expired_deadline() {
     let result = router.try_remove_liquidity(
         &token_a,
         &token_b,
+        &STANDARD_FEE_TIER,
         &100i128,
         &50i128,
         &50i128,
@@ -182,8 +985,14 @@ fn test_remove_liquidity_zero_amount() {
     let deadline = env.ledger().timestamp() + 1000;
 
     let result = router.try_remove_liquidity(
-        &token_a, &token_b, &0i128, // zero liquidity
-        &50i128, &50i128, &to, &deadline,
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &0i128, // zero liquidity
+        &50i128,
+        &50i128,
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, Err(Ok(RouterError::ZeroAmount)));
@@ -204,8 +1013,14 @@ fn test_remove_liquidity_identical_tokens() {
 
     // Pass the same address for both tokens
     let result = router.try_remove_liquidity(
-        &token_a, &token_a, // identical
-        &100i128, &50i128, &50i128, &to, &deadline,
+        &token_a,
+        &token_a,
+        &STANDARD_FEE_TIER, // identical
+        &100i128,
+        &50i128,
+        &50i128,
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, Err(Ok(RouterError::IdenticalTokens)));
@@ -229,8 +1044,16 @@ fn test_remove_liquidity_pair_not_found() {
     // Factory has no pair registered
     let deadline = env.ledger().timestamp() + 1000;
 
-    let result =
-        router.try_remove_liquidity(&token_a, &token_b, &100i128, &50i128, &50i128, &to, &deadline);
+    let result = router.try_remove_liquidity(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &100i128,
+        &50i128,
+        &50i128,
+        &to,
+        &deadline,
+    );
 
     assert_eq!(result, Err(Ok(RouterError::PairNotFound)));
 }
@@ -251,10 +1074,14 @@ fn test_remove_liquidity_happy_path() {
 
     // Mock pair returns (500, 1000) on burn; minimums are below those values
     let result = router_client.remove_liquidity(
-        &token_a, &token_b, &100i128, // liquidity to burn
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &100i128, // liquidity to burn
         &400i128, // amount_a_min (below 500 returned)
         &900i128, // amount_b_min (below 1000 returned)
-        &to, &deadline,
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, (500, 1000));
@@ -276,9 +1103,14 @@ fn test_remove_liquidity_exact_minimums() {
 
     // amount_a_min == amount_a and amount_b_min == amount_b (edge case)
     let result = router_client.remove_liquidity(
-        &token_a, &token_b, &100i128, &500i128,  // exactly equal to returned amount_a
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &100i128,
+        &500i128,  // exactly equal to returned amount_a
         &1000i128, // exactly equal to returned amount_b
-        &to, &deadline,
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, (500, 1000));
@@ -300,8 +1132,14 @@ fn test_remove_liquidity_insufficient_output_amount_a() {
 
     // amount_a_min > returned amount_a (500) → slippage revert
     let result = router_client.try_remove_liquidity(
-        &token_a, &token_b, &100i128, &600i128, // above 500 returned
-        &900i128, &to, &deadline,
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &100i128,
+        &600i128, // above 500 returned
+        &900i128,
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, Err(Ok(RouterError::InsufficientOutputAmount)));
@@ -323,8 +1161,14 @@ fn test_remove_liquidity_insufficient_output_amount_b() {
 
     // amount_b_min > returned amount_b (1000) → slippage revert
     let result = router_client.try_remove_liquidity(
-        &token_a, &token_b, &100i128, &400i128, &1100i128, // above 1000 returned
-        &to, &deadline,
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &100i128,
+        &400i128,
+        &1100i128, // above 1000 returned
+        &to,
+        &deadline,
     );
 
     assert_eq!(result, Err(Ok(RouterError::InsufficientOutputAmount)));
@@ -348,8 +1192,16 @@ fn test_remove_liquidity_lp_tokens_transferred() {
     let balance_before = lp_token.balance(&to);
 
     let liquidity: i128 = 200;
-    router_client
-        .remove_liquidity(&token_a, &token_b, &liquidity, &400i128, &900i128, &to, &deadline);
+    router_client.remove_liquidity(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &liquidity,
+        &400i128,
+        &900i128,
+        &to,
+        &deadline,
+    );
 
     // LP tokens should have been transferred from 'to' to pair
     let balance_after = lp_token.balance(&to);
@@ -359,3 +1211,323 @@ fn test_remove_liquidity_lp_tokens_transferred() {
     let pair_balance = lp_token.balance(&pair_addr);
     assert_eq!(pair_balance, liquidity);
 }
+
+#[test]
+fn test_remove_liquidity_uses_requested_fee_tier() {
+    let (env, router_client, token_a, token_b, to, deadline, _, lp_token_addr, pair_addr) =
+        setup_full_env();
+    let factory_addr =
+        env.as_contract(&router_client.address, || crate::storage::get_factory(&env).unwrap());
+    let factory = MockFactoryClient::new(&env, &factory_addr);
+    let stable_pair = MockPairClient::new(&env, &env.register_contract(None, MockPair));
+    stable_pair.set_lp_token(&lp_token_addr);
+    stable_pair.set_burn_amounts(&70, &80);
+    factory.set_pair(&token_a, &token_b, &5, &stable_pair.address);
+
+    let result =
+        router_client.remove_liquidity(&token_a, &token_b, &5, &100, &70, &80, &to, &deadline);
+
+    assert_eq!(result, (70, 80));
+    let lp_token = TokenClient::new(&env, &lp_token_addr);
+    assert_eq!(lp_token.balance(&stable_pair.address), 100);
+    assert_eq!(lp_token.balance(&pair_addr), 0);
+}
+
+// ── remove_liquidity_with_permit tests ────────────────────────────────────────
+
+/// An LP holder whose account is controlled by an ed25519 key.
+struct PermitHolder {
+    key: ed25519_dalek::SigningKey,
+    address: Address,
+}
+
+impl PermitHolder {
+    fn new(env: &Env) -> Self {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+        let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes());
+        let address = Address::from_string(&String::from_str(env, &strkey.to_string()));
+        PermitHolder { key, address }
+    }
+
+    fn public_key(&self, env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &self.key.verifying_key().to_bytes())
+    }
+
+    /// Signs a permit letting `spender` pull `amount` LP tokens.
+    fn sign(
+        &self,
+        env: &Env,
+        lp_token: &LpTokenClient,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Permit {
+        use ed25519_dalek::Signer as _;
+
+        let message = PermitMessage {
+            domain_separator: lp_token.domain_separator(),
+            owner: self.address.clone(),
+            spender: spender.clone(),
+            amount,
+            expiration_ledger,
+            nonce: lp_token.nonce(&self.address),
+        };
+        let payload: std::vec::Vec<u8> = message.to_xdr(env).iter().collect();
+        Permit {
+            expiration_ledger,
+            public_key: self.public_key(env),
+            signature: BytesN::from_array(env, &self.key.sign(&payload).to_bytes()),
+        }
+    }
+}
+
+/// Like `setup_full_env`, but the pair's LP token is a real CoralSwap LP
+/// token holding 2000 LP for a key-backed holder. Auths are not mocked.
+///
+/// Returns (env, router_client, token_a, token_b, holder, deadline,
+///          lp_token_client, pair_addr).
+#[allow(clippy::type_complexity)]
+fn setup_permit_env() -> (
+    Env,
+    RouterClient<'static>,
+    Address,
+    Address,
+    PermitHolder,
+    u64,
+    LpTokenClient<'static>,
+    Address,
+) {
+    let (env, router_client, token_a, token_b, _, deadline, mock_pair_client, _, pair_addr) =
+        setup_full_env();
+
+    let lp_token = LpTokenClient::new(&env, &env.register_contract(None, LpToken));
+    let lp_admin = Address::generate(&env);
+    lp_token.initialize(
+        &lp_admin,
+        &7,
+        &String::from_str(&env, "CORAL-LP A/B"),
+        &String::from_str(&env, "CLP-A-B"),
+    );
+    mock_pair_client.set_lp_token(&lp_token.address);
+
+    let holder = PermitHolder::new(&env);
+    lp_token.mint(&holder.address, &2000);
+    env.set_auths(&[]);
+
+    (env, router_client, token_a, token_b, holder, deadline, lp_token, pair_addr)
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_happy_path() {
+    let (env, router_client, token_a, token_b, holder, deadline, lp_token, pair_addr) =
+        setup_permit_env();
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+
+    env.mock_all_auths();
+    let (amount_a, amount_b) = router_client.remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &400,
+        &900,
+        &holder.address,
+        &deadline,
+        &permit,
+    );
+
+    // The holder authorizes only the router call; the LP transfer rides the permit.
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, holder.address);
+    assert!(auths[0].1.sub_invocations.is_empty());
+
+    assert_eq!((amount_a, amount_b), (500, 1000));
+    assert_eq!(lp_token.balance(&holder.address), 1800);
+    assert_eq!(lp_token.balance(&pair_addr), 200);
+    assert_eq!(lp_token.allowance(&holder.address, &router_client.address), 0);
+    assert_eq!(lp_token.nonce(&holder.address), 1);
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_survives_front_run() {
+    let (env, router_client, token_a, token_b, holder, deadline, lp_token, pair_addr) =
+        setup_permit_env();
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+
+    // Someone watching the mempool submits the permit to the LP token first.
+    lp_token.permit(
+        &holder.address,
+        &router_client.address,
+        &200,
+        &permit.expiration_ledger,
+        &permit.public_key,
+        &permit.signature,
+    );
+    assert_eq!(lp_token.nonce(&holder.address), 1);
+
+    env.mock_all_auths();
+    let (amount_a, amount_b) = router_client.remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &400,
+        &900,
+        &holder.address,
+        &deadline,
+        &permit,
+    );
+
+    assert_eq!((amount_a, amount_b), (500, 1000));
+    assert_eq!(lp_token.balance(&pair_addr), 200);
+    assert_eq!(lp_token.allowance(&holder.address, &router_client.address), 0);
+    assert_eq!(lp_token.nonce(&holder.address), 1);
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_requires_holder_auth() {
+    let (env, router_client, token_a, token_b, holder, deadline, lp_token, _) = setup_permit_env();
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+
+    // A third party replaying the holder's permit with no slippage protection.
+    let result = router_client.try_remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &0,
+        &0,
+        &holder.address,
+        &deadline,
+        &permit,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(lp_token.balance(&holder.address), 2000);
+    assert_eq!(lp_token.nonce(&holder.address), 0);
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_existing_allowance_requires_holder_auth() {
+    let (env, router_client, token_a, token_b, holder, deadline, lp_token, _) = setup_permit_env();
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+    lp_token.permit(
+        &holder.address,
+        &router_client.address,
+        &200,
+        &permit.expiration_ledger,
+        &permit.public_key,
+        &permit.signature,
+    );
+
+    let result = router_client.try_remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &0,
+        &0,
+        &holder.address,
+        &deadline,
+        &permit,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(lp_token.balance(&holder.address), 2000);
+    assert_eq!(lp_token.allowance(&holder.address, &router_client.address), 200);
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_rejects_other_signer() {
+    let (env, router_client, token_a, token_b, holder, deadline, lp_token, _) = setup_permit_env();
+    let impostor = Address::generate(&env);
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+
+    env.mock_all_auths();
+    let result = router_client.try_remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &400,
+        &900,
+        &impostor,
+        &deadline,
+        &permit,
+    );
+
+    assert!(result.is_err());
+    assert_eq!(lp_token.balance(&holder.address), 2000);
+}
+
+#[test]
+fn test_remove_liquidity_with_permit_expired_deadline() {
+    let (env, router_client, token_a, token_b, holder, _, lp_token, _) = setup_permit_env();
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    let permit = holder.sign(&env, &lp_token, &router_client.address, 200, 1_000);
+
+    let result = router_client.try_remove_liquidity_with_permit(
+        &token_a,
+        &token_b,
+        &STANDARD_FEE_TIER,
+        &200,
+        &400,
+        &900,
+        &holder.address,
+        &1999,
+        &permit,
+    );
+
+    assert_eq!(result, Err(Ok(RouterError::Expired)));
+}
+
+// ── TTL tests ─────────────────────────────────────────────────────────────────
+
+fn setup_router() -> (Env, RouterClient<'static>) {
+    let env = Env::default();
+    let router_client = RouterClient::new(&env, &env.register_contract(None, Router));
+    router_client.initialize(&Address::generate(&env));
+    (env, router_client)
+}
+
+#[test]
+fn test_initialize_extends_instance_ttl() {
+    let (env, router_client) = setup_router();
+
+    env.as_contract(&router_client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+    });
+}
+
+#[test]
+fn test_factory_read_extends_instance_ttl() {
+    let (env, router_client) = setup_router();
+    env.ledger().with_mut(|li| li.sequence_number += INSTANCE_BUMP_AMOUNT - 10);
+
+    env.as_contract(&router_client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), 10);
+        assert!(crate::storage::get_factory(&env).is_some());
+        assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+    });
+}
+
+#[test]
+fn test_bump_extends_instance_ttl() {
+    let (env, router_client) = setup_router();
+    env.ledger().with_mut(|li| li.sequence_number += INSTANCE_BUMP_AMOUNT - 10);
+
+    router_client.bump();
+
+    env.as_contract(&router_client.address, || {
+        assert_eq!(env.storage().instance().get_ttl(), INSTANCE_BUMP_AMOUNT);
+    });
+}
+
+#[test]
+fn test_bump_requires_initialization() {
+    let env = Env::default();
+    let router_client = RouterClient::new(&env, &env.register_contract(None, Router));
+
+    assert_eq!(router_client.try_bump(), Err(Ok(RouterError::NotInitialized)));
+}
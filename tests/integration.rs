@@ -1,10 +1,282 @@
 #[cfg(test)]
 mod integration_tests {
-    use soroban_sdk::Env;
+    use coralswap_factory::{testutils::install_pair, Factory, FactoryClient};
+    use coralswap_lp_token::{LpToken, LpTokenClient, PermitMessage};
+    use coralswap_pair::{Pair, PairClient};
+    use coralswap_router::{Permit, Router, RouterClient};
+    use ed25519_dalek::{Signer as _, SigningKey};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        xdr::ToXdr,
+        Address, Bytes, BytesN, Env, String, Vec,
+    };
+
+    const STANDARD_FEE_TIER: u32 = 30;
+    const EXOTIC_FEE_TIER: u32 = 100;
+
+    /// Real factory, router, pairs and LP tokens wired together.
+    ///
+    /// The factory can only deploy pairs from wasm, which native tests don't
+    /// have, so pools are registered natively and handed to the factory with
+    /// `testutils::install_pair`, which runs the rest of `create_pair`.
+    /// The underlying assets are plain SEP-41 tokens rather than Stellar
+    /// assets so that key-backed accounts can hold them without trustlines.
+    struct Protocol<'a> {
+        env: Env,
+        factory: FactoryClient<'a>,
+        router: RouterClient<'a>,
+        token_a: LpTokenClient<'a>,
+        token_b: LpTokenClient<'a>,
+    }
+
+    impl<'a> Protocol<'a> {
+        fn new() -> Self {
+            let env = Env::default();
+            env.mock_all_auths();
+            env.budget().reset_unlimited();
+
+            let factory = FactoryClient::new(&env, &env.register_contract(None, Factory));
+            let wasm_hash = env.deployer().upload_contract_wasm(Bytes::new(&env));
+            factory.initialize(
+                &Vec::from_array(&env, [Address::generate(&env)]),
+                &wasm_hash,
+                &wasm_hash,
+                &Address::generate(&env),
+            );
+
+            let router = RouterClient::new(&env, &env.register_contract(None, Router));
+            router.initialize(&factory.address);
+
+            let t0 = Self::token(&env, "TKA");
+            let t1 = Self::token(&env, "TKB");
+            let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+            Protocol {
+                factory,
+                router,
+                token_a: LpTokenClient::new(&env, &t0),
+                token_b: LpTokenClient::new(&env, &t1),
+                env,
+            }
+        }
+
+        fn token(env: &Env, symbol: &str) -> Address {
+            let token = LpTokenClient::new(env, &env.register_contract(None, LpToken));
+            token.initialize(
+                &Address::generate(env),
+                &7,
+                &String::from_str(env, symbol),
+                &String::from_str(env, symbol),
+            );
+            token.address
+        }
+
+        /// Registers a real pair and LP token for the protocol's tokens in
+        /// `fee_tier`.
+        fn add_pool(&self, fee_tier: u32) -> (PairClient<'a>, LpTokenClient<'a>) {
+            let pair = PairClient::new(&self.env, &self.env.register_contract(None, Pair));
+            let lp_token =
+                LpTokenClient::new(&self.env, &self.env.register_contract(None, LpToken));
+            install_pair(
+                &self.env,
+                &self.factory.address,
+                &self.token_a.address,
+                &self.token_b.address,
+                fee_tier,
+                &pair.address,
+                &lp_token.address,
+            );
+            (pair, lp_token)
+        }
+
+        /// A single-hop path from `token_in` to `token_out`.
+        fn path(&self, token_in: &LpTokenClient, token_out: &LpTokenClient) -> Vec<Address> {
+            Vec::from_array(&self.env, [token_in.address.clone(), token_out.address.clone()])
+        }
+
+        /// Deposits `amount_a` / `amount_b` straight into `pair`, minting LP to `to`.
+        fn seed(&self, pair: &PairClient, amount_a: i128, amount_b: i128, to: &Address) -> i128 {
+            self.token_a.mint(&pair.address, &amount_a);
+            self.token_b.mint(&pair.address, &amount_b);
+            pair.mint(to)
+        }
+    }
+
+    /// An LP holder whose account is controlled by an ed25519 key.
+    struct Holder {
+        key: SigningKey,
+        address: Address,
+    }
+
+    impl Holder {
+        fn new(env: &Env) -> Self {
+            let key = SigningKey::from_bytes(&[42; 32]);
+            let strkey = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes());
+            let address = Address::from_string(&String::from_str(env, &strkey.to_string()));
+            Holder { key, address }
+        }
+
+        /// Signs a permit letting `spender` pull `amount` of `lp_token`.
+        fn sign_permit(
+            &self,
+            env: &Env,
+            lp_token: &LpTokenClient,
+            spender: &Address,
+            amount: i128,
+            expiration_ledger: u32,
+        ) -> Permit {
+            let message = PermitMessage {
+                domain_separator: lp_token.domain_separator(),
+                owner: self.address.clone(),
+                spender: spender.clone(),
+                amount,
+                expiration_ledger,
+                nonce: lp_token.nonce(&self.address),
+            };
+            let payload: std::vec::Vec<u8> = message.to_xdr(env).iter().collect();
+            Permit {
+                expiration_ledger,
+                public_key: BytesN::from_array(env, &self.key.verifying_key().to_bytes()),
+                signature: BytesN::from_array(env, &self.key.sign(&payload).to_bytes()),
+            }
+        }
+    }
 
     #[test]
-    fn test_placeholder_full_swap_flow() {
-        let _env = Env::default();
+    fn test_remove_liquidity_with_permit_rejects_third_parties() {
+        let protocol = Protocol::new();
+        let env = &protocol.env;
+        let (pair, lp_token) = protocol.add_pool(STANDARD_FEE_TIER);
+        let holder = Holder::new(env);
+        let liquidity = protocol.seed(&pair, 1_000_000, 4_000_000, &holder.address);
+        let permit = holder.sign_permit(env, &lp_token, &protocol.router.address, liquidity, 1_000);
+        let remove = |amount_min: i128| {
+            protocol.router.try_remove_liquidity_with_permit(
+                &protocol.token_a.address,
+                &protocol.token_b.address,
+                &STANDARD_FEE_TIER,
+                &liquidity,
+                &amount_min,
+                &amount_min,
+                &holder.address,
+                &u64::MAX,
+                &permit,
+            )
+        };
+
+        // A third party holding the permit can't exit for the holder.
+        env.set_auths(&[]);
+        assert!(remove(0).is_err());
+        assert_eq!(lp_token.balance(&holder.address), liquidity);
+
+        // The holder can, authorizing only the router call itself.
+        env.mock_all_auths();
+        let (amount_a, amount_b) = remove(1).unwrap().unwrap();
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, holder.address);
+        assert!(auths[0].1.sub_invocations.is_empty());
+
+        assert_eq!(lp_token.balance(&holder.address), 0);
+        assert_eq!(protocol.token_a.balance(&holder.address), amount_a);
+        assert_eq!(protocol.token_b.balance(&holder.address), amount_b);
+        assert!(amount_a > 0 && amount_b > 0);
+    }
+
+    /// Seeds a pool in the exotic tier priced at 1e8 token_b per token_a, then
+    /// swaps back and forth across ledgers until volatility has pushed its
+    /// dynamic fee off the tier's baseline.
+    ///
+    /// Returns the pair and a trader holding plenty of both tokens.
+    fn volatile_pool<'a>(protocol: &Protocol<'a>) -> (PairClient<'a>, Address) {
+        let env = &protocol.env;
+        let (pair, _) = protocol.add_pool(EXOTIC_FEE_TIER);
+        protocol.seed(&pair, 1_000_000, 100_000_000_000_000, &Address::generate(env));
+        let trader = Address::generate(env);
+        protocol.token_a.mint(&trader, &100_000_000);
+        protocol.token_b.mint(&trader, &10_000_000_000_000_000);
+
+        let there = protocol.path(&protocol.token_a, &protocol.token_b);
+        let back = protocol.path(&protocol.token_b, &protocol.token_a);
+        let fee_tiers = Vec::from_array(env, [EXOTIC_FEE_TIER]);
+        for round in 0..4u64 {
+            env.ledger().set_timestamp(100 * (round + 1));
+            let amounts = protocol.router.swap_exact_tokens_for_tokens(
+                &500_000,
+                &0,
+                &there,
+                &fee_tiers,
+                &trader,
+                &u64::MAX,
+            );
+            protocol.router.swap_exact_tokens_for_tokens(
+                &amounts.get_unchecked(1),
+                &0,
+                &back,
+                &fee_tiers,
+                &trader,
+                &u64::MAX,
+            );
+        }
+        env.ledger().set_timestamp(1_000);
+        assert_ne!(pair.get_current_fee_bps(), EXOTIC_FEE_TIER);
+
+        (pair, trader)
+    }
+
+    #[test]
+    fn test_router_quotes_match_what_the_pair_accepts() {
+        let protocol = Protocol::new();
+        let (pair, trader) = volatile_pool(&protocol);
+        let path = protocol.path(&protocol.token_a, &protocol.token_b);
+        let fee_tiers = Vec::from_array(&protocol.env, [EXOTIC_FEE_TIER]);
+
+        // Exact input: the quoted output is the most the pair will release.
+        let quoted = protocol.router.get_amounts_out(&50_000, &path, &fee_tiers);
+        let amount_out = quoted.get_unchecked(1);
+        protocol.token_a.transfer(&trader, &pair.address, &50_000);
+        assert!(pair.try_swap(&0, &(amount_out + 1), &trader).is_err());
+        pair.swap(&0, &amount_out, &trader);
+
+        // Exact output: the quoted input is the least the pair will take.
+        let amount_out = 1_000_000_000_000;
+        let quoted = protocol.router.get_amounts_in(&amount_out, &path, &fee_tiers);
+        let amount_in = quoted.get_unchecked(0);
+        protocol.token_a.transfer(&trader, &pair.address, &(amount_in - 1));
+        assert!(pair.try_swap(&0, &amount_out, &trader).is_err());
+        protocol.token_a.transfer(&trader, &pair.address, &1);
+        pair.swap(&0, &amount_out, &trader);
+    }
+
+    #[test]
+    fn test_router_swaps_execute_at_quoted_amounts() {
+        let protocol = Protocol::new();
+        let (_, trader) = volatile_pool(&protocol);
+        let path = protocol.path(&protocol.token_b, &protocol.token_a);
+        let fee_tiers = Vec::from_array(&protocol.env, [EXOTIC_FEE_TIER]);
+
+        let amount_in = 3_000_000_000_000;
+        let quoted = protocol.router.get_amounts_out(&amount_in, &path, &fee_tiers);
+        let executed = protocol.router.swap_exact_tokens_for_tokens(
+            &amount_in,
+            &0,
+            &path,
+            &fee_tiers,
+            &trader,
+            &u64::MAX,
+        );
+        assert_eq!(executed, quoted);
+
+        let quoted = protocol.router.get_amounts_in(&10_000, &path, &fee_tiers);
+        let executed = protocol.router.swap_tokens_for_exact_tokens(
+            &10_000,
+            &i128::MAX,
+            &path,
+            &fee_tiers,
+            &trader,
+            &u64::MAX,
+        );
+        assert_eq!(executed, quoted);
     }
 
     #[test]